@@ -1124,7 +1124,7 @@ impl Evaluator {
             ),
             function_tool(
                 "shell",
-                "Execute a read-only shell command in safety whitelist.",
+                "Run read-only whitelisted commands (pipes, &&, || and ; allowed; no redirection, substitution or variables).",
                 serde_json::json!({
                     "cmd": {"type": "string"}
                 }),
@@ -1146,6 +1146,14 @@ impl Evaluator {
                 }),
                 &["path"],
             ),
+            function_tool(
+                "verify",
+                "Run the project's configured build/test commands in a scratch copy of the working tree. Returns pass/fail and failing test names. Optional command selects one configured command by index or substring.",
+                serde_json::json!({
+                    "command": {"type": "string"}
+                }),
+                &[],
+            ),
         ]
     }
 
@@ -1756,30 +1764,27 @@ impl Evaluator {
                                             let _ = tx.send(event).await;
                                         }
                                     }
-                                    "response.output_item.done" => {
-                                        if !saw_output_text_delta {
-                                            if let Some(text) =
-                                                Self::responses_output_item_text(&event)
-                                            {
-                                                buffer.push_str(&text);
-
-                                                if buffer.contains(THINKING_RESPONSE_DELIMITER) {
-                                                    in_thinking = false;
-                                                    let parts: Vec<&str> = buffer
-                                                        .splitn(2, THINKING_RESPONSE_DELIMITER)
-                                                        .collect();
-                                                    if parts.len() == 2 {
-                                                        buffer = parts[1].to_string();
-                                                    }
-                                                }
+                                    "response.output_item.done" if !saw_output_text_delta => {
+                                        if let Some(text) = Self::responses_output_item_text(&event)
+                                        {
+                                            buffer.push_str(&text);
 
-                                                let stream_event = if in_thinking {
-                                                    GugugagaThinking::Thinking(text)
-                                                } else {
-                                                    GugugagaThinking::Response(text)
-                                                };
-                                                let _ = tx.send(stream_event).await;
+                                            if buffer.contains(THINKING_RESPONSE_DELIMITER) {
+                                                in_thinking = false;
+                                                let parts: Vec<&str> = buffer
+                                                    .splitn(2, THINKING_RESPONSE_DELIMITER)
+                                                    .collect();
+                                                if parts.len() == 2 {
+                                                    buffer = parts[1].to_string();
+                                                }
                                             }
+
+                                            let stream_event = if in_thinking {
+                                                GugugagaThinking::Thinking(text)
+                                            } else {
+                                                GugugagaThinking::Response(text)
+                                            };
+                                            let _ = tx.send(stream_event).await;
                                         }
                                     }
                                     "response.completed" | "response.done" => {
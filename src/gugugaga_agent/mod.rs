@@ -3,24 +3,30 @@
 //! The gugugaga agent uses LLM to evaluate Codex behavior and decide actions.
 
 mod evaluator;
+mod path_guard;
+mod read_only_shell;
 mod responder;
+mod verify;
 
 pub use evaluator::{
     Evaluator, GugugagaThinking, ParsedResponse, StructuredToolCall, StructuredTurnResponse,
 };
+use path_guard::{PathDenied, PathGuard};
+use read_only_shell::{ReadOnlyShell, ShellRejection};
 pub use responder::Responder;
+use verify::Verifier;
 
 use crate::memory::compact::DEFAULT_CONTEXT_WINDOW;
 use crate::memory::{
     AttentionSource, Compactor, ContextBuilder, GugugagaNotebook, PersistentMemory, Priority,
 };
+use crate::project_config::ProjectConfig;
 use crate::rules::Violation;
 use crate::Result;
 use glob::glob;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::process::Command;
 use tokio::sync::RwLock;
 
 /// Result of violation detection
@@ -71,6 +77,15 @@ pub struct GugugagaAgent {
 
     /// Gugugaga's personal notebook (never compacted)
     notebook: Arc<RwLock<GugugagaNotebook>>,
+
+    /// Confines file tools to the project and extra read roots
+    path_guard: PathGuard,
+
+    /// Read-only command runner for the shell/rg/ls tools
+    shell: ReadOnlyShell,
+
+    /// Runs the project's configured build/test commands for `verify`
+    verifier: Verifier,
 }
 
 /// Result of evaluating a user input request
@@ -96,14 +111,35 @@ impl GugugagaAgent {
         let evaluator = Evaluator::new(codex_home).await?;
         let responder = Responder::new();
 
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let path_guard = PathGuard::new(cwd.clone(), &[]);
+
         Ok(Self {
             evaluator,
             responder,
             memory,
             notebook,
+            shell: ReadOnlyShell::new(path_guard.clone()),
+            path_guard,
+            verifier: Verifier::new(cwd, &ProjectConfig::default()),
         })
     }
 
+    /// Confine file tools to `cwd` plus any `extra_read_roots`
+    pub fn with_read_roots(mut self, cwd: PathBuf, extra_read_roots: &[PathBuf]) -> Self {
+        self.path_guard = PathGuard::new(cwd, extra_read_roots);
+        self.shell = ReadOnlyShell::new(self.path_guard.clone());
+        self
+    }
+
+    /// Enable the `verify` tool with the project's configured commands.
+    /// Build caches shared between runs live under `cache_dir`.
+    pub fn with_verification(mut self, config: &ProjectConfig, cache_dir: PathBuf) -> Self {
+        self.verifier = Verifier::new(self.path_guard.project_root().to_path_buf(), config)
+            .with_cache_dir(cache_dir);
+        self
+    }
+
     /// Get the evaluator (for compaction)
     pub fn evaluator(&self) -> &Evaluator {
         &self.evaluator
@@ -468,6 +504,9 @@ impl GugugagaAgent {
             "shell" => pick_str(&["cmd", "command"]).ok_or_else(|| "missing cmd".to_string()),
             "rg" => pick_str(&["pattern"]).ok_or_else(|| "missing pattern".to_string()),
             "ls" => pick_str(&["path"]).ok_or_else(|| "missing path".to_string()),
+            "verify" => Ok(pick_str(&["command"])
+                .or_else(|| pick_usize(&["index"]).map(|i| i.to_string()))
+                .unwrap_or_default()),
             _ => {
                 if let Some(s) = value.as_str() {
                     Ok(s.to_string())
@@ -658,9 +697,33 @@ impl GugugagaAgent {
 
         let started = std::time::Instant::now();
         let normalized = Self::normalize_tool_arguments(&tool_call.tool_name, &tool_call.arguments);
+        if let Ok(ref normalized_args) = normalized {
+            if let Err(denied) = self.check_tool_paths(&tool_call.tool_name, normalized_args) {
+                tracing::warn!("Denied {} access: {}", tool_call.tool_name, denied);
+                let output = self.path_guard.denial_result(&tool_call.tool_name, &denied);
+                self.emit_tool_call_completed_event(
+                    tool_call,
+                    event_tx,
+                    &output,
+                    ToolCallEventMeta {
+                        duration_ms: started.elapsed().as_millis() as u64,
+                        success: false,
+                        normalized_args: Some(normalized_args),
+                        normalized_error: None,
+                        guarded: true,
+                        duplicate: false,
+                        notebook_diff: None,
+                    },
+                );
+                return Some(ToolExecutionOutcome {
+                    call_id: tool_call.call_id.clone(),
+                    tool_result: output,
+                });
+            }
+        }
         let (result, normalized_args, normalized_error) = match normalized {
             Ok(normalized_args) => (
-                self.execute_tool_call(&tool_call.tool_name, &normalized_args)
+                self.execute_tool_call(&tool_call.tool_name, &normalized_args, event_tx)
                     .await,
                 Some(normalized_args),
                 None,
@@ -708,7 +771,12 @@ impl GugugagaAgent {
     }
 
     /// Execute a tool call.
-    async fn execute_tool_call(&self, tool_name: &str, args: &str) -> Option<String> {
+    async fn execute_tool_call(
+        &self,
+        tool_name: &str,
+        args: &str,
+        event_tx: Option<&tokio::sync::mpsc::Sender<String>>,
+    ) -> Option<String> {
         match tool_name {
             // === History query tools ===
             "search_history" => {
@@ -939,30 +1007,92 @@ impl GugugagaAgent {
             }
             "shell" | "rg" | "grep" => {
                 // Execute shell command (with safety restrictions)
-                let cmd = if tool_name == "rg" || tool_name == "grep" {
-                    // Use rg instead of grep.
-                    format!("rg {}", args)
-                } else {
-                    args.to_string()
-                };
+                let cmd = Self::shell_command_for(tool_name, args);
 
-                match self.execute_shell(&cmd).await {
+                match self.shell.run(&cmd).await {
                     Ok(output) => Some(format!("shell(\"{}\"):\n{}", cmd, output)),
                     Err(e) => Some(format!("shell(\"{}\"): Error: {}", cmd, e)),
                 }
             }
             "ls" => {
                 // List directory
-                let cmd = format!("ls -la {}", args);
-                match self.execute_shell(&cmd).await {
+                let cmd = Self::shell_command_for(tool_name, args);
+                match self.shell.run(&cmd).await {
                     Ok(output) => Some(format!("ls(\"{}\"):\n{}", args, output)),
                     Err(e) => Some(format!("ls(\"{}\"): Error: {}", args, e)),
                 }
             }
+            "verify" => Some(self.run_verify(args, event_tx).await),
             _ => Some(format!("Unknown tool: {}", tool_name)),
         }
     }
 
+    /// Run configured verification commands, reporting progress to the TUI
+    /// as `gugugaga/verify` events. Returns the JSON report as tool output.
+    async fn run_verify(
+        &self,
+        selector: &str,
+        event_tx: Option<&tokio::sync::mpsc::Sender<String>>,
+    ) -> String {
+        let commands = match self.verifier.select(selector) {
+            Ok(commands) => commands,
+            Err(e) => return format!("verify: Error: {}", e),
+        };
+        let emit = |params: serde_json::Value| {
+            if let Some(tx) = event_tx {
+                let msg = serde_json::json!({
+                    "method": "gugugaga/verify",
+                    "params": params,
+                });
+                let _ = tx.try_send(msg.to_string());
+            }
+        };
+
+        emit(serde_json::json!({ "status": "started", "commands": &commands }));
+        match self.verifier.run(&commands).await {
+            Ok(report) => {
+                tracing::info!("verify: {}", report.summary());
+                emit(serde_json::json!({ "status": "completed", "report": &report }));
+                serde_json::to_string(&report).unwrap_or_else(|e| format!("verify: Error: {}", e))
+            }
+            Err(e) => {
+                emit(serde_json::json!({ "status": "failed", "error": &e }));
+                format!("verify: Error: {}", e)
+            }
+        }
+    }
+
+    /// Command line run by the shell-backed tools
+    fn shell_command_for(tool_name: &str, args: &str) -> String {
+        match tool_name {
+            // Use rg instead of grep.
+            "rg" | "grep" => format!("rg {}", args),
+            "ls" => format!("ls -la {}", args),
+            _ => args.to_string(),
+        }
+    }
+
+    /// Path confinement check for file tools, run before anything executes
+    /// so denials can be reported as guarded tool calls.
+    fn check_tool_paths(&self, tool_name: &str, args: &str) -> std::result::Result<(), PathDenied> {
+        match tool_name {
+            "read_file" => {
+                let path = args.split('|').next().unwrap_or("").trim();
+                self.path_guard
+                    .resolve(self.path_guard.project_root(), path)
+                    .map(|_| ())
+            }
+            "glob" => self.glob_pattern(args).map(|_| ()),
+            "shell" | "rg" | "grep" | "ls" => {
+                match self.shell.check(&Self::shell_command_for(tool_name, args)) {
+                    Err(ShellRejection::Denied(denied)) => Err(denied),
+                    _ => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Read file lines with offset and limit
     async fn read_file_lines(
         &self,
@@ -970,7 +1100,10 @@ impl GugugagaAgent {
         offset: usize,
         limit: usize,
     ) -> std::result::Result<String, String> {
-        let path = PathBuf::from(path);
+        let path = self
+            .path_guard
+            .resolve(self.path_guard.project_root(), path)
+            .map_err(|e| format!("Access denied: {}", e))?;
 
         let content = tokio::fs::read_to_string(&path)
             .await
@@ -989,18 +1122,46 @@ impl GugugagaAgent {
         Ok(result.join("\n"))
     }
 
-    /// Find files matching glob pattern
-    async fn glob_files(&self, pattern: &str) -> std::result::Result<Vec<PathBuf>, String> {
-        let pattern = if pattern.starts_with('/') || pattern.starts_with('.') {
-            pattern.to_string()
+    /// Anchor a glob pattern at the project root and check its static prefix.
+    fn glob_pattern(&self, pattern: &str) -> std::result::Result<PathBuf, PathDenied> {
+        let root = self.path_guard.project_root();
+        let full = if pattern.starts_with('/') || pattern.starts_with('.') {
+            root.join(pattern)
         } else {
-            format!("**/{}", pattern)
+            root.join("**").join(pattern)
         };
+        let prefix: PathBuf = full
+            .components()
+            .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+            .collect();
+        self.path_guard.resolve(root, &prefix.to_string_lossy())?;
+        Ok(full)
+    }
+
+    /// Find files matching glob pattern, relative to the project root.
+    /// Matches outside the allowed roots or on the denylist are dropped.
+    async fn glob_files(&self, pattern: &str) -> std::result::Result<Vec<PathBuf>, String> {
+        let pattern = self
+            .glob_pattern(pattern)
+            .map_err(|e| format!("Access denied: {}", e))?;
+        let root = self.path_guard.project_root();
 
         let mut files = Vec::new();
-        for entry in glob(&pattern).map_err(|e| format!("Invalid pattern: {}", e))? {
+        for entry in
+            glob(&pattern.to_string_lossy()).map_err(|e| format!("Invalid pattern: {}", e))?
+        {
             match entry {
-                Ok(path) => files.push(path),
+                Ok(path) => {
+                    let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+                    if self.path_guard.check(&canonical).is_err() {
+                        continue;
+                    }
+                    files.push(
+                        path.strip_prefix(root)
+                            .map(Path::to_path_buf)
+                            .unwrap_or(path),
+                    );
+                }
                 Err(_) => continue,
             }
         }
@@ -1008,66 +1169,6 @@ impl GugugagaAgent {
         Ok(files)
     }
 
-    /// Execute a shell command with safety restrictions (whitelist-based like Codex)
-    async fn execute_shell(&self, cmd: &str) -> std::result::Result<String, String> {
-        // Parse command into parts
-        let parts: Vec<&str> = cmd.split_whitespace().collect();
-        if parts.is_empty() {
-            return Err("Empty command".to_string());
-        }
-
-        let program = std::path::Path::new(parts[0])
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or(parts[0]);
-
-        // Whitelist-based safety check (like Codex's is_known_safe_command)
-        if !self.is_safe_command(program, &parts) {
-            return Err(format!("Command '{}' is not in the safe whitelist. Allowed: cat, ls, head, tail, wc, grep, rg, find (without -exec/-delete), git (status/log/diff/show/branch), file, stat, which, pwd, echo, tree", program));
-        }
-
-        let output = Command::new("sh")
-            .arg("-c")
-            .arg(cmd)
-            .output()
-            .await
-            .map_err(|e| format!("Failed to execute: {}", e))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-
-        // Limit output size
-        let max_len = 4000;
-        let mut result = String::new();
-
-        if !stdout.is_empty() {
-            let s = if stdout.len() > max_len {
-                format!("{}...[truncated]", &stdout[..max_len])
-            } else {
-                stdout.to_string()
-            };
-            result.push_str(&s);
-        }
-
-        if !stderr.is_empty() {
-            if !result.is_empty() {
-                result.push_str("\n[stderr]\n");
-            }
-            let s = if stderr.len() > max_len {
-                format!("{}...[truncated]", &stderr[..max_len])
-            } else {
-                stderr.to_string()
-            };
-            result.push_str(&s);
-        }
-
-        if result.is_empty() {
-            result = "(no output)".to_string();
-        }
-
-        Ok(result)
-    }
-
     /// Handle update_notebook tool with JSON format
     async fn handle_update_notebook(&self, args: &str) -> Option<String> {
         let trimmed = args.trim();
@@ -1176,82 +1277,6 @@ impl GugugagaAgent {
         }
     }
 
-    /// Check if a command is safe to execute (whitelist-based, matching Codex exactly)
-    fn is_safe_command(&self, program: &str, args: &[&str]) -> bool {
-        match program {
-            // Codex's exact whitelist from is_known_safe_command
-            "cat" | "cd" | "cut" | "echo" | "expr" | "false" | "grep" | "head" | "id" | "ls"
-            | "nl" | "paste" | "pwd" | "rev" | "seq" | "stat" | "tail" | "tr" | "true"
-            | "uname" | "uniq" | "wc" | "which" | "whoami" => true,
-
-            // Linux-specific (Codex allows on Linux)
-            "numfmt" | "tac" => cfg!(target_os = "linux"),
-
-            // base64 - safe without output options
-            "base64" => {
-                let unsafe_opts = ["-o", "--output"];
-                !args.iter().any(|arg| {
-                    unsafe_opts.contains(arg)
-                        || arg.starts_with("--output=")
-                        || (arg.starts_with("-o") && *arg != "-o")
-                })
-            }
-
-            // find - safe without exec/delete options
-            "find" => {
-                let unsafe_opts = [
-                    "-exec", "-execdir", "-ok", "-okdir", "-delete", "-fls", "-fprint", "-fprint0",
-                    "-fprintf",
-                ];
-                !args.iter().any(|arg| unsafe_opts.contains(arg))
-            }
-
-            // ripgrep - safe except for dangerous options
-            "rg" => !args.iter().any(|arg| {
-                *arg == "--search-zip"
-                    || *arg == "-z"
-                    || *arg == "--pre"
-                    || arg.starts_with("--pre=")
-                    || *arg == "--hostname-bin"
-                    || arg.starts_with("--hostname-bin=")
-            }),
-
-            // git - only safe subcommands (exactly as Codex)
-            "git" => {
-                matches!(
-                    args.get(1),
-                    Some(&"branch")
-                        | Some(&"status")
-                        | Some(&"log")
-                        | Some(&"diff")
-                        | Some(&"show")
-                )
-            }
-
-            // cargo - only cargo check (exactly as Codex)
-            "cargo" => {
-                matches!(args.get(1), Some(&"check"))
-            }
-
-            // sed - only safe patterns like `sed -n 1,5p`
-            "sed" => {
-                args.len() <= 4
-                    && args.get(1) == Some(&"-n")
-                    && args.get(2).is_some_and(|arg| {
-                        // Check if it matches pattern like "1p" or "1,5p"
-                        arg.ends_with('p')
-                            && arg
-                                .trim_end_matches('p')
-                                .chars()
-                                .all(|c| c.is_ascii_digit() || c == ',')
-                    })
-            }
-
-            // Anything else is not allowed
-            _ => false,
-        }
-    }
-
     /// Parse check response using JSON-first strategy with text fallback.
     /// Never returns Err — unparseable responses are treated as "OK".
     fn parse_check_response(
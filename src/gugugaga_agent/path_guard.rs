@@ -0,0 +1,283 @@
+//! Path confinement for the supervisor's file tools.
+//!
+//! Every path a tool touches is canonicalized and must fall inside the
+//! project root or an explicitly configured extra read root. Secrets
+//! (`.env`, private keys, `auth.json`, ...) are denied even inside a root.
+
+use std::path::{Component, Path, PathBuf};
+
+/// Directory names whose contents are never readable
+const SENSITIVE_DIRS: &[&str] = &[".ssh", ".gnupg", ".aws", ".kube", ".docker"];
+
+/// Exact file names that are never readable
+const SENSITIVE_FILES: &[&str] = &[
+    ".env",
+    "auth.json",
+    ".netrc",
+    ".npmrc",
+    ".pypirc",
+    "credentials",
+    "credentials.json",
+    "id_rsa",
+    "id_dsa",
+    "id_ecdsa",
+    "id_ed25519",
+];
+
+/// File extensions that are never readable
+const SENSITIVE_EXTENSIONS: &[&str] = &["pem", "key", "p12", "pfx", "jks", "keystore"];
+
+/// `rg --glob` exclusions mirroring the denylist, so recursive searches
+/// don't print secrets either.
+pub(crate) const SENSITIVE_RG_GLOBS: &[&str] = &[
+    "!.env",
+    "!.env.*",
+    "!auth.json",
+    "!.netrc",
+    "!.npmrc",
+    "!.pypirc",
+    "!id_rsa*",
+    "!id_dsa*",
+    "!id_ecdsa*",
+    "!id_ed25519*",
+    "!*.pem",
+    "!*.key",
+    "!*.p12",
+    "!*.pfx",
+    "!.ssh/",
+    "!.gnupg/",
+    "!.aws/",
+];
+
+/// Why a path was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DenyReason {
+    OutsideRoots,
+    Sensitive,
+}
+
+impl DenyReason {
+    fn code(&self) -> &'static str {
+        match self {
+            DenyReason::OutsideRoots => "outside_allowed_roots",
+            DenyReason::Sensitive => "sensitive_file",
+        }
+    }
+}
+
+/// A refused path access
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PathDenied {
+    pub path: String,
+    pub reason: DenyReason,
+}
+
+impl std::fmt::Display for PathDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.reason {
+            DenyReason::OutsideRoots => write!(
+                f,
+                "'{}' is outside the project and configured read roots",
+                self.path
+            ),
+            DenyReason::Sensitive => write!(f, "'{}' may contain secrets", self.path),
+        }
+    }
+}
+
+/// Confines paths to a set of canonical roots; the first is the project.
+#[derive(Debug, Clone)]
+pub(crate) struct PathGuard {
+    roots: Vec<PathBuf>,
+}
+
+impl PathGuard {
+    pub(crate) fn new(project_root: PathBuf, extra_roots: &[PathBuf]) -> Self {
+        let mut roots = vec![canonicalize_lenient(&project_root)];
+        for root in extra_roots {
+            let root = canonicalize_lenient(&roots[0].join(root));
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        Self { roots }
+    }
+
+    pub(crate) fn project_root(&self) -> &Path {
+        &self.roots[0]
+    }
+
+    /// Resolve `raw` against `base` and check it. `~` expands to the home
+    /// directory so the denial names the real target.
+    pub(crate) fn resolve(&self, base: &Path, raw: &str) -> Result<PathBuf, PathDenied> {
+        let expanded = match raw.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => dirs::home_dir()
+                .map(|home| home.join(rest.trim_start_matches('/')))
+                .unwrap_or_else(|| PathBuf::from(raw)),
+            _ => PathBuf::from(raw),
+        };
+        let path = canonicalize_lenient(&base.join(expanded));
+        self.check(&path)?;
+        Ok(path)
+    }
+
+    /// Check an already-resolved path.
+    pub(crate) fn check(&self, path: &Path) -> Result<(), PathDenied> {
+        let denied = |reason| PathDenied {
+            path: path.display().to_string(),
+            reason,
+        };
+        if is_sensitive(path) {
+            return Err(denied(DenyReason::Sensitive));
+        }
+        if !self.roots.iter().any(|root| path.starts_with(root)) {
+            return Err(denied(DenyReason::OutsideRoots));
+        }
+        Ok(())
+    }
+
+    /// Structured error handed back to the model as the tool result.
+    pub(crate) fn denial_result(&self, tool: &str, denied: &PathDenied) -> String {
+        serde_json::json!({
+            "error": "access_denied",
+            "tool": tool,
+            "path": denied.path,
+            "reason": denied.reason.code(),
+            "message": denied.to_string(),
+            "allowed_roots": self
+                .roots
+                .iter()
+                .map(|r| r.display().to_string())
+                .collect::<Vec<_>>(),
+        })
+        .to_string()
+    }
+}
+
+/// Whether a path names a file that commonly holds credentials.
+pub(crate) fn is_sensitive(path: &Path) -> bool {
+    let in_sensitive_dir = path.components().any(|c| match c {
+        Component::Normal(name) => SENSITIVE_DIRS.contains(&name.to_string_lossy().as_ref()),
+        _ => false,
+    });
+    if in_sensitive_dir {
+        return true;
+    }
+    let Some(name) = path.file_name().map(|n| n.to_string_lossy()) else {
+        return false;
+    };
+    if SENSITIVE_FILES.contains(&name.as_ref()) {
+        return true;
+    }
+    // `.env.local`, `.env.production`, ... but not `.env.example`.
+    if let Some(variant) = name.strip_prefix(".env.") {
+        return !matches!(variant, "example" | "sample" | "template" | "dist");
+    }
+    if SENSITIVE_FILES
+        .iter()
+        .filter(|f| f.starts_with("id_"))
+        .any(|f| name.starts_with(f) && !name.ends_with(".pub"))
+    {
+        return true;
+    }
+    path.extension()
+        .map(|ext| SENSITIVE_EXTENSIONS.contains(&ext.to_string_lossy().as_ref()))
+        .unwrap_or(false)
+}
+
+/// Canonicalize the longest existing prefix and normalize the rest
+/// lexically, so non-existent paths still resolve symlinks and `..`.
+pub(crate) fn canonicalize_lenient(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    let mut existing = path.to_path_buf();
+    let mut tail = Vec::new();
+    while let Some(name) = existing.file_name().map(|n| n.to_os_string()) {
+        existing.pop();
+        tail.push(name);
+        if let Ok(canonical) = existing.canonicalize() {
+            let mut out = canonical;
+            for component in tail.iter().rev() {
+                out.push(component);
+            }
+            return normalize(&out);
+        }
+    }
+    normalize(path)
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn confines_to_roots_and_denies_secrets() {
+        let project = TempDir::new().unwrap();
+        let extra = TempDir::new().unwrap();
+        std::fs::write(project.path().join("main.rs"), "").unwrap();
+        std::fs::write(extra.path().join("notes.md"), "").unwrap();
+        let guard = PathGuard::new(project.path().to_path_buf(), &[extra.path().to_path_buf()]);
+        let base = guard.project_root().to_path_buf();
+
+        assert!(guard.resolve(&base, "main.rs").is_ok());
+        assert!(guard.resolve(&base, "src/not_yet.rs").is_ok());
+        assert!(guard
+            .resolve(&base, &extra.path().join("notes.md").display().to_string())
+            .is_ok());
+
+        for (raw, reason) in [
+            ("../", DenyReason::OutsideRoots),
+            ("/etc/passwd", DenyReason::OutsideRoots),
+            ("~/.codex/auth.json", DenyReason::Sensitive),
+            ("~/.ssh/config", DenyReason::Sensitive),
+            (".env", DenyReason::Sensitive),
+            ("config/.env.production", DenyReason::Sensitive),
+            ("certs/server.key", DenyReason::Sensitive),
+            ("deploy/id_ed25519", DenyReason::Sensitive),
+        ] {
+            let denied = guard.resolve(&base, raw).unwrap_err();
+            assert_eq!(denied.reason, reason, "{}", raw);
+        }
+        assert!(guard.resolve(&base, ".env.example").is_ok());
+        assert!(guard.resolve(&base, "deploy/id_ed25519.pub").is_ok());
+
+        let result = guard.denial_result(
+            "read_file",
+            &guard.resolve(&base, "/etc/passwd").unwrap_err(),
+        );
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(json["error"], "access_denied");
+        assert_eq!(json["reason"], "outside_allowed_roots");
+        assert_eq!(json["allowed_roots"].as_array().unwrap().len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_resolved_before_checking() {
+        let project = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        std::os::unix::fs::symlink(outside.path(), project.path().join("link")).unwrap();
+        let guard = PathGuard::new(project.path().to_path_buf(), &[]);
+        let base = guard.project_root().to_path_buf();
+        assert_eq!(
+            guard.resolve(&base, "link/anything").unwrap_err().reason,
+            DenyReason::OutsideRoots
+        );
+    }
+}
@@ -0,0 +1,738 @@
+//! Read-only command runner behind the supervisor's `shell`, `rg` and `ls` tools.
+//!
+//! The command line is parsed with [`crate::rules::shell`] and every command
+//! in every pipeline and list is checked against a whitelist before anything
+//! runs. Redirections (other than to `/dev/null` or fd duplication),
+//! parameter expansion, command substitution, subshells and background jobs
+//! are rejected outright; globs are expanded here, and every path argument
+//! must pass the [`PathGuard`]. Validated commands are exec'd directly —
+//! never through `sh -c` — under a timeout and an output cap.
+
+use super::path_guard::{DenyReason, PathDenied, PathGuard, SENSITIVE_RG_GLOBS};
+use crate::rules::shell::{self, Command as ShellCommand, ListOp, RedirectKind, Word};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+
+/// Wall-clock limit for one tool invocation
+const SHELL_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Maximum bytes of stdout (and, separately, stderr) returned to the model
+const MAX_OUTPUT_BYTES: usize = 4000;
+
+/// A validated command ready to exec
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlannedCommand {
+    program: String,
+    args: Vec<String>,
+    cwd: PathBuf,
+    stdout_null: bool,
+    stderr_null: bool,
+}
+
+/// A validated pipeline; `cd` is resolved at plan time and never exec'd
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlannedPipeline {
+    commands: Vec<PlannedCommand>,
+    terminator: ListOp,
+}
+
+/// Why a command line was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ShellRejection {
+    /// Touches a path outside the allowed roots or on the denylist
+    Denied(PathDenied),
+    /// Unparseable, not whitelisted, or not provably read-only
+    Invalid(String),
+}
+
+impl std::fmt::Display for ShellRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShellRejection::Denied(denied) => write!(f, "Access denied: {}", denied),
+            ShellRejection::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl From<String> for ShellRejection {
+    fn from(msg: String) -> Self {
+        ShellRejection::Invalid(msg)
+    }
+}
+
+impl From<PathDenied> for ShellRejection {
+    fn from(denied: PathDenied) -> Self {
+        ShellRejection::Denied(denied)
+    }
+}
+
+/// Runs whitelisted, read-only commands confined by a [`PathGuard`].
+#[derive(Debug, Clone)]
+pub(crate) struct ReadOnlyShell {
+    guard: PathGuard,
+}
+
+impl ReadOnlyShell {
+    pub(crate) fn new(guard: PathGuard) -> Self {
+        Self { guard }
+    }
+
+    /// Validate and run a command line.
+    pub(crate) async fn run(&self, cmd: &str) -> std::result::Result<String, String> {
+        let plan = self.plan(cmd).map_err(|e| e.to_string())?;
+        match tokio::time::timeout(SHELL_TIMEOUT, execute_plan(&plan)).await {
+            Ok(result) => result.map_err(|e| format!("Failed to execute: {}", e)),
+            Err(_) => Err(format!(
+                "Command timed out after {}s",
+                SHELL_TIMEOUT.as_secs()
+            )),
+        }
+    }
+
+    /// Validate a command line without running it.
+    pub(crate) fn check(&self, cmd: &str) -> std::result::Result<(), ShellRejection> {
+        self.plan(cmd).map(|_| ())
+    }
+
+    /// Parse and validate without executing anything.
+    fn plan(&self, cmd: &str) -> std::result::Result<Vec<PlannedPipeline>, ShellRejection> {
+        if cmd.trim().is_empty() {
+            return Err("Empty command".to_string().into());
+        }
+        let script = shell::parse(cmd).map_err(|e| e.to_string())?;
+
+        let mut cwd = self.guard.project_root().to_path_buf();
+        let mut plan = Vec::new();
+        for pipeline in &script.pipelines {
+            if pipeline.terminator == ListOp::Background {
+                return Err("Background jobs (`&`) are not allowed".to_string().into());
+            }
+            let mut commands = Vec::new();
+            for command in &pipeline.commands {
+                let simple = match command {
+                    ShellCommand::Simple(simple) => simple,
+                    ShellCommand::Subshell { .. } => {
+                        return Err("Subshells and command groups are not allowed"
+                            .to_string()
+                            .into())
+                    }
+                };
+                if !simple.assignments.is_empty() {
+                    return Err("Environment assignments are not allowed".to_string().into());
+                }
+                for word in simple
+                    .words
+                    .iter()
+                    .chain(simple.redirects.iter().map(|r| &r.target))
+                {
+                    check_word(word)?;
+                }
+
+                let mut stdout_null = false;
+                let mut stderr_null = false;
+                for redirect in &simple.redirects {
+                    match redirect.kind {
+                        // `2>&1` and friends: both streams are captured anyway.
+                        RedirectKind::Duplicate
+                            if redirect.target.text.chars().all(|c| c.is_ascii_digit()) => {}
+                        RedirectKind::Output | RedirectKind::Append
+                            if redirect.target.text == "/dev/null" =>
+                        {
+                            match redirect.fd {
+                                None | Some(1) => stdout_null = true,
+                                Some(2) => stderr_null = true,
+                                Some(fd) => {
+                                    return Err(
+                                        format!("Redirection of fd {} is not allowed", fd).into()
+                                    )
+                                }
+                            }
+                        }
+                        _ => {
+                            return Err(format!(
+                            "Redirection to '{}' is not allowed: the supervisor shell is read-only",
+                            redirect.target.text
+                        )
+                            .into())
+                        }
+                    }
+                }
+
+                let Some(program_word) = simple.words.first() else {
+                    return Err("Redirection without a command is not allowed"
+                        .to_string()
+                        .into());
+                };
+                let program = program_word.text.as_str();
+                if program.contains('/') {
+                    return Err(format!(
+                        "'{}': run whitelisted commands by name, not by path",
+                        program
+                    )
+                    .into());
+                }
+
+                if program == "cd" {
+                    if pipeline.commands.len() > 1 {
+                        return Err("`cd` cannot be part of a pipeline".to_string().into());
+                    }
+                    let target = simple.words.get(1).map(|w| w.text.as_str()).unwrap_or(".");
+                    cwd = self.resolve_dir(&cwd, target)?;
+                    continue;
+                }
+
+                let mut argv = vec![program.to_string()];
+                if program == "rg" {
+                    // Keep recursive searches away from secrets too.
+                    argv.extend(SENSITIVE_RG_GLOBS.iter().map(|g| format!("--glob={}", g)));
+                }
+                for word in &simple.words[1..] {
+                    if word.glob {
+                        argv.extend(self.expand_glob(&cwd, &word.text)?);
+                    } else if word.text.starts_with('~') && !word.quoted {
+                        let path = self.guard.resolve(&cwd, &word.text)?;
+                        argv.push(path.to_string_lossy().into_owned());
+                    } else {
+                        self.check_path_argument(&cwd, &word.text)?;
+                        argv.push(word.text.clone());
+                    }
+                }
+                let argv_refs: Vec<&str> = argv.iter().map(String::as_str).collect();
+                if !is_safe_command(program, &argv_refs) {
+                    return Err(format!(
+                        "Command '{}' is not in the read-only whitelist. Allowed: cat, ls, head, tail, wc, grep, rg, find (without -exec/-delete), git (status/log/diff/show/branch), sed -n, stat, which, pwd, echo",
+                        argv.join(" ")
+                    ).into());
+                }
+
+                commands.push(PlannedCommand {
+                    program: program.to_string(),
+                    args: argv[1..].to_vec(),
+                    cwd: cwd.clone(),
+                    stdout_null,
+                    stderr_null,
+                });
+            }
+            if !commands.is_empty() {
+                plan.push(PlannedPipeline {
+                    commands,
+                    terminator: pipeline.terminator,
+                });
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Check arguments that name a path: absolute, `..`-relative, or an
+    /// existing file. Option values (`--file=x`) are checked the same way.
+    fn check_path_argument(&self, cwd: &Path, arg: &str) -> std::result::Result<(), PathDenied> {
+        let value = match arg.strip_prefix('-') {
+            Some(_) => match arg.split_once('=') {
+                Some((_, value)) => value,
+                None => return Ok(()),
+            },
+            None => arg,
+        };
+        let looks_like_path = value.starts_with('/')
+            || value.split('/').any(|part| part == "..")
+            || cwd.join(value).symlink_metadata().is_ok();
+        if looks_like_path {
+            self.guard.resolve(cwd, value)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_dir(
+        &self,
+        cwd: &Path,
+        target: &str,
+    ) -> std::result::Result<PathBuf, ShellRejection> {
+        let dir = self.guard.resolve(cwd, target)?;
+        if !dir.is_dir() {
+            return Err(format!("cd {}: not a directory", target).into());
+        }
+        Ok(dir)
+    }
+
+    /// Expand a glob relative to `cwd`; every match must pass the guard,
+    /// except that secrets are silently left out of the expansion.
+    /// A pattern with no matches is passed through literally, as a shell would.
+    fn expand_glob(
+        &self,
+        cwd: &Path,
+        pattern: &str,
+    ) -> std::result::Result<Vec<String>, ShellRejection> {
+        let absolute = Path::new(pattern).is_absolute();
+        let full = if absolute {
+            PathBuf::from(pattern)
+        } else {
+            cwd.join(pattern)
+        };
+        let entries = glob::glob(&full.to_string_lossy())
+            .map_err(|e| format!("Invalid glob '{}': {}", pattern, e))?;
+
+        let mut matches = Vec::new();
+        for path in entries.flatten() {
+            let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+            match self.guard.check(&canonical) {
+                Ok(()) => {}
+                Err(denied) if denied.reason == DenyReason::Sensitive => continue,
+                Err(denied) => return Err(denied.into()),
+            }
+            let shown = if absolute {
+                path
+            } else {
+                path.strip_prefix(cwd)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(path)
+            };
+            matches.push(shown.to_string_lossy().into_owned());
+        }
+
+        if matches.is_empty() {
+            let static_prefix: PathBuf = full
+                .components()
+                .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+                .collect();
+            self.guard
+                .resolve(cwd, &static_prefix.to_string_lossy())
+                .or_else(|denied| match denied.reason {
+                    DenyReason::Sensitive => Ok(PathBuf::new()),
+                    DenyReason::OutsideRoots => Err(denied),
+                })?;
+            matches.push(pattern.to_string());
+        }
+        Ok(matches)
+    }
+}
+
+fn check_word(word: &Word) -> std::result::Result<(), ShellRejection> {
+    if word.is_dynamic() {
+        return Err(format!(
+            "'{}': parameter expansion and command substitution are not allowed",
+            word.text
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Check if a command is safe to execute (whitelist-based, like Codex's
+/// `is_known_safe_command`, minus anything that writes).
+/// `args` includes the program itself at index 0.
+fn is_safe_command(program: &str, args: &[&str]) -> bool {
+    match program {
+        "cat" | "cut" | "echo" | "expr" | "false" | "head" | "id" | "ls" | "nl" | "paste"
+        | "pwd" | "rev" | "seq" | "stat" | "tail" | "tr" | "true" | "uname" | "uniq" | "wc"
+        | "which" | "whoami" => true,
+
+        // grep - single files only; recursive search goes through rg, which
+        // skips secrets
+        "grep" => !args.iter().skip(1).any(|arg| {
+            *arg == "--recursive"
+                || *arg == "--dereference-recursive"
+                || arg.starts_with("--directories")
+                || (arg.starts_with('-') && !arg.starts_with("--") && arg.contains(['r', 'R', 'd']))
+        }),
+
+        // Linux-specific (Codex allows on Linux)
+        "numfmt" | "tac" => cfg!(target_os = "linux"),
+
+        // base64 - safe without output options
+        "base64" => {
+            let unsafe_opts = ["-o", "--output"];
+            !args.iter().any(|arg| {
+                unsafe_opts.contains(arg)
+                    || arg.starts_with("--output=")
+                    || (arg.starts_with("-o") && *arg != "-o")
+            })
+        }
+
+        // find - safe without exec/delete options
+        "find" => {
+            let unsafe_opts = [
+                "-exec", "-execdir", "-ok", "-okdir", "-delete", "-fls", "-fprint", "-fprint0",
+                "-fprintf",
+            ];
+            !args.iter().any(|arg| unsafe_opts.contains(arg))
+        }
+
+        // ripgrep - safe except for dangerous options
+        "rg" => !args.iter().any(|arg| {
+            *arg == "--search-zip"
+                || *arg == "-z"
+                || *arg == "--pre"
+                || arg.starts_with("--pre=")
+                || *arg == "--hostname-bin"
+                || arg.starts_with("--hostname-bin=")
+        }),
+
+        // git - only read-only subcommands, and no options that write files
+        // or run external programs
+        "git" => {
+            let writes = args.iter().any(|arg| {
+                *arg == "--output"
+                    || arg.starts_with("--output=")
+                    || *arg == "--ext-diff"
+                    || *arg == "--textconv"
+            });
+            !writes
+                && match args.get(1) {
+                    Some(&"status") | Some(&"log") | Some(&"diff") | Some(&"show") => true,
+                    Some(&"branch") => is_read_only_git_branch(&args[2..]),
+                    _ => false,
+                }
+        }
+
+        // sed - only safe patterns like `sed -n 1,5p`
+        "sed" => {
+            args.len() <= 4
+                && args.get(1) == Some(&"-n")
+                && args.get(2).is_some_and(|arg| {
+                    // Check if it matches pattern like "1p" or "1,5p"
+                    arg.ends_with('p')
+                        && arg
+                            .trim_end_matches('p')
+                            .chars()
+                            .all(|c| c.is_ascii_digit() || c == ',')
+                })
+        }
+
+        // Anything else is not allowed
+        _ => false,
+    }
+}
+
+/// `git branch` only lists unless given a positional name outside list mode
+/// or one of the create/delete/rename flags.
+fn is_read_only_git_branch(args: &[&str]) -> bool {
+    const MUTATING: &[&str] = &[
+        "-d",
+        "-D",
+        "--delete",
+        "-m",
+        "-M",
+        "--move",
+        "-c",
+        "-C",
+        "--copy",
+        "-f",
+        "--force",
+        "-u",
+        "--set-upstream-to",
+        "--unset-upstream",
+        "--edit-description",
+        "-t",
+        "--track",
+    ];
+    const TAKES_VALUE: &[&str] = &[
+        "--contains",
+        "--no-contains",
+        "--merged",
+        "--no-merged",
+        "--points-at",
+        "--sort",
+        "--format",
+    ];
+    let list_mode = args.iter().any(|a| *a == "--list" || *a == "-l");
+    let mut previous: Option<&str> = None;
+    for arg in args {
+        if MUTATING.contains(arg) || arg.starts_with("--set-upstream-to=") {
+            return false;
+        }
+        if !arg.starts_with('-')
+            && !list_mode
+            && !previous.is_some_and(|p| TAKES_VALUE.contains(&p))
+        {
+            return false;
+        }
+        previous = Some(arg);
+    }
+    true
+}
+
+struct PipelineOutput {
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    stdout_truncated: bool,
+    stderr_truncated: bool,
+    exit_code: Option<i32>,
+}
+
+async fn execute_plan(plan: &[PlannedPipeline]) -> std::io::Result<String> {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut stdout_truncated = false;
+    let mut stderr_truncated = false;
+    let mut last_exit = Some(0);
+
+    let mut skip_next = false;
+    for pipeline in plan {
+        if !skip_next {
+            let output = run_pipeline(&pipeline.commands).await?;
+            stdout.extend(output.stdout);
+            stderr.extend(output.stderr);
+            stdout_truncated |= output.stdout_truncated;
+            stderr_truncated |= output.stderr_truncated;
+            last_exit = output.exit_code;
+            if stdout_truncated || stdout.len() > MAX_OUTPUT_BYTES {
+                break;
+            }
+        }
+        // `a && b` skips b after a failure, `a || b` after a success. A
+        // skipped pipeline keeps the previous status, so `a && b && c`
+        // skips both b and c when a fails.
+        skip_next = match pipeline.terminator {
+            ListOp::And => last_exit != Some(0),
+            ListOp::Or => last_exit == Some(0),
+            ListOp::Seq | ListOp::Background => false,
+        };
+    }
+
+    let mut result = String::new();
+    if !stdout.is_empty() {
+        result.push_str(&capped_text(&stdout, stdout_truncated));
+    }
+    if !stderr.is_empty() {
+        if !result.is_empty() {
+            result.push_str("\n[stderr]\n");
+        }
+        result.push_str(&capped_text(&stderr, stderr_truncated));
+    }
+    if result.is_empty() {
+        result = "(no output)".to_string();
+    }
+    // After truncation the producers were killed, so the status is noise.
+    if !stdout_truncated && !stderr_truncated {
+        match last_exit {
+            Some(0) => {}
+            Some(code) => result.push_str(&format!("\n[exit status {}]", code)),
+            None => result.push_str("\n[terminated by signal]"),
+        }
+    }
+    Ok(result)
+}
+
+fn capped_text(bytes: &[u8], truncated: bool) -> String {
+    let end = bytes.len().min(MAX_OUTPUT_BYTES);
+    let text = String::from_utf8_lossy(&bytes[..end]);
+    if truncated || bytes.len() > MAX_OUTPUT_BYTES {
+        format!("{}...[truncated]", text)
+    } else {
+        text.into_owned()
+    }
+}
+
+async fn run_pipeline(commands: &[PlannedCommand]) -> std::io::Result<PipelineOutput> {
+    let mut children = Vec::with_capacity(commands.len());
+    let mut previous_stdout: Option<Stdio> = None;
+    for (i, planned) in commands.iter().enumerate() {
+        let is_last = i + 1 == commands.len();
+        let mut command = Command::new(&planned.program);
+        command
+            .args(&planned.args)
+            .current_dir(&planned.cwd)
+            .kill_on_drop(true)
+            .stdin(previous_stdout.take().unwrap_or_else(Stdio::null))
+            .stdout(if planned.stdout_null {
+                Stdio::null()
+            } else {
+                Stdio::piped()
+            })
+            .stderr(if planned.stderr_null {
+                Stdio::null()
+            } else {
+                Stdio::piped()
+            });
+        let mut child = command.spawn()?;
+        if !is_last {
+            if let Some(out) = child.stdout.take() {
+                previous_stdout = Some(out.try_into()?);
+            }
+        }
+        children.push(child);
+    }
+
+    let last_stdout = children.last_mut().and_then(|c| c.stdout.take());
+    let stderr_pipes: Vec<_> = children.iter_mut().map(|c| c.stderr.take()).collect();
+
+    let read_stdout = async {
+        match last_stdout {
+            Some(out) => read_capped(out).await,
+            None => Ok((Vec::new(), false)),
+        }
+    };
+    let read_stderr = futures::future::join_all(stderr_pipes.into_iter().map(|pipe| async {
+        match pipe {
+            Some(err) => read_capped(err).await,
+            None => Ok((Vec::new(), false)),
+        }
+    }));
+    let (stdout_result, stderr_results) = tokio::join!(read_stdout, read_stderr);
+    let (stdout, stdout_truncated) = stdout_result?;
+    let mut stderr = Vec::new();
+    let mut stderr_truncated = false;
+    for result in stderr_results {
+        let (bytes, cut) = result?;
+        stderr.extend(bytes);
+        stderr_truncated |= cut;
+    }
+
+    if stdout_truncated || stderr_truncated {
+        // Stop producers that would otherwise block on a full pipe.
+        for child in &mut children {
+            let _ = child.start_kill();
+        }
+    }
+    let mut exit_code = None;
+    for child in &mut children {
+        exit_code = child.wait().await?.code();
+    }
+
+    Ok(PipelineOutput {
+        stdout,
+        stderr,
+        stdout_truncated,
+        stderr_truncated,
+        exit_code,
+    })
+}
+
+/// Read up to `MAX_OUTPUT_BYTES + 1` bytes; the flag reports truncation.
+async fn read_capped<R: AsyncRead + Unpin>(reader: R) -> std::io::Result<(Vec<u8>, bool)> {
+    let mut buf = Vec::new();
+    reader
+        .take(MAX_OUTPUT_BYTES as u64 + 1)
+        .read_to_end(&mut buf)
+        .await?;
+    let truncated = buf.len() > MAX_OUTPUT_BYTES;
+    buf.truncate(MAX_OUTPUT_BYTES);
+    Ok((buf, truncated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn shell() -> (TempDir, ReadOnlyShell) {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(dir.path().join("src/b.rs"), "fn b() {}\n").unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=secret\n").unwrap();
+        let shell = ReadOnlyShell::new(PathGuard::new(dir.path().to_path_buf(), &[]));
+        (dir, shell)
+    }
+
+    #[test]
+    fn rejects_every_write_or_escape_vector() {
+        let (_dir, shell) = shell();
+        for cmd in [
+            "cat src/a.rs; rm -rf x",
+            "ls $(touch y)",
+            "ls `touch y`",
+            "echo hi > file",
+            "echo hi >> file",
+            "cat < /etc/passwd",
+            "ls $HOME",
+            "ls ~",
+            "FOO=1 ls",
+            "(ls)",
+            "ls &",
+            "cat /etc/*",
+            "ls ../*",
+            "cd ..",
+            "/tmp/evil/cat x",
+            "git branch new-branch",
+            "git branch -D main",
+            "git diff --output=x",
+            "cargo check",
+            "grep -rn secret .",
+            "echo 'unterminated",
+        ] {
+            assert!(shell.plan(cmd).is_err(), "should reject: {}", cmd);
+        }
+    }
+
+    #[test]
+    fn path_arguments_are_guarded() {
+        let (_dir, shell) = shell();
+        for cmd in [
+            "cat /etc/passwd",
+            "ls -la ~",
+            "cat .env",
+            "head -n 5 src/../.env",
+            "rg token /etc",
+            "cat /etc/*",
+            "cd ..",
+        ] {
+            assert!(
+                matches!(shell.check(cmd), Err(ShellRejection::Denied(_))),
+                "should deny: {}",
+                cmd
+            );
+        }
+        assert!(matches!(
+            shell.check("ls $HOME"),
+            Err(ShellRejection::Invalid(_))
+        ));
+
+        // Secrets drop out of glob expansion, and rg gets exclusion globs.
+        let plan = shell.plan("cat * && rg TOKEN").unwrap();
+        assert!(!plan[0].commands[0].args.iter().any(|a| a == ".env"));
+        assert!(plan[1].commands[0]
+            .args
+            .contains(&"--glob=!.env".to_string()));
+    }
+
+    #[test]
+    fn plans_pipelines_lists_and_globs() {
+        let (_dir, shell) = shell();
+        let plan = shell
+            .plan("cd src && ls *.rs 2>/dev/null | wc -l; git branch --show-current")
+            .unwrap();
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].commands.len(), 2);
+        let ls = &plan[0].commands[0];
+        assert_eq!(ls.args, vec!["a.rs".to_string(), "b.rs".to_string()]);
+        assert!(ls.cwd.ends_with("src"));
+        assert!(ls.stderr_null);
+        assert!(shell.plan("rg 'fn .*' src 2>&1").is_ok());
+        assert!(shell.plan("git branch --contains HEAD").is_ok());
+    }
+
+    #[tokio::test]
+    async fn runs_without_a_shell() {
+        let (_dir, shell) = shell();
+        let output = shell.run("cat src/a.rs src/b.rs | wc -l").await.unwrap();
+        assert_eq!(output.trim(), "2");
+
+        // `||` short-circuits and `&&` skips after failure.
+        let output = shell
+            .run("false && echo skipped || echo recovered")
+            .await
+            .unwrap();
+        assert_eq!(output.trim(), "recovered");
+
+        // Metacharacters inside quotes are passed through literally.
+        let output = shell.run("echo '$(touch pwned)'").await.unwrap();
+        assert_eq!(output.trim(), "$(touch pwned)");
+        assert!(!_dir.path().join("pwned").exists());
+    }
+
+    #[tokio::test]
+    async fn caps_output() {
+        let (_dir, shell) = shell();
+        let output = shell.run("seq 1 100000").await.unwrap();
+        assert!(
+            output.ends_with("...[truncated]"),
+            "{}",
+            &output[output.len() - 40..]
+        );
+        assert!(output.len() < MAX_OUTPUT_BYTES + 100);
+    }
+}
@@ -0,0 +1,435 @@
+//! Verification runner for the supervisor's `verify` tool.
+//!
+//! Runs the project's configured build/test commands against a snapshot of
+//! the working tree checked out in a scratch git worktree (or a plain copy
+//! for non-git projects), so nothing Codex is editing is touched. Commands
+//! come only from `.gugugaga/config.toml`; the model can pick one of them
+//! but never supply its own.
+
+use crate::project_config::ProjectConfig;
+use crate::snapshot;
+use regex::Regex;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+/// Bytes of combined output kept from the end of each command
+const OUTPUT_TAIL_BYTES: usize = 3000;
+
+/// Failing test names reported per command
+const MAX_FAILING_TESTS: usize = 50;
+
+/// Heavy directories symlinked into (rather than copied to) the scratch tree
+const SHARED_DIRS: &[&str] = &["node_modules", ".venv", "venv"];
+
+/// Outcome of one verification command
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CommandReport {
+    pub command: String,
+    pub passed: bool,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub duration_ms: u64,
+    pub failing_tests: Vec<String>,
+    pub output_tail: String,
+}
+
+/// Outcome of a verification run
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct VerifyReport {
+    pub passed: bool,
+    pub commands: Vec<CommandReport>,
+}
+
+impl VerifyReport {
+    /// One-line summary for status displays
+    pub(crate) fn summary(&self) -> String {
+        let failed: Vec<&str> = self
+            .commands
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.command.as_str())
+            .collect();
+        if failed.is_empty() {
+            format!("{} command(s) passed", self.commands.len())
+        } else {
+            format!("failed: {}", failed.join(", "))
+        }
+    }
+}
+
+/// Runs configured verification commands in a scratch copy of the project
+#[derive(Debug)]
+pub(crate) struct Verifier {
+    project_root: PathBuf,
+    commands: Vec<String>,
+    timeout: Duration,
+    /// Shared `CARGO_TARGET_DIR` so repeated runs stay incremental
+    cache_dir: Option<PathBuf>,
+    /// One run at a time; test suites rarely tolerate concurrency
+    running: Mutex<()>,
+}
+
+impl Verifier {
+    pub(crate) fn new(project_root: PathBuf, config: &ProjectConfig) -> Self {
+        Self {
+            project_root,
+            commands: config.verify.clone(),
+            timeout: config.verify_timeout(),
+            cache_dir: None,
+            running: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn with_cache_dir(mut self, dir: PathBuf) -> Self {
+        self.cache_dir = Some(dir);
+        self
+    }
+
+    /// Pick configured commands: all of them, or those matching `selector`
+    /// (a 0-based index or a substring).
+    pub(crate) fn select(&self, selector: &str) -> Result<Vec<String>, String> {
+        if self.commands.is_empty() {
+            return Err(format!(
+                "no verification commands configured; add `verify = [\"...\"]` to {}",
+                ProjectConfig::path(&self.project_root).display()
+            ));
+        }
+        let selector = selector.trim();
+        if selector.is_empty() {
+            return Ok(self.commands.clone());
+        }
+        if let Ok(index) = selector.parse::<usize>() {
+            if let Some(cmd) = self.commands.get(index) {
+                return Ok(vec![cmd.clone()]);
+            }
+        }
+        let matched: Vec<String> = self
+            .commands
+            .iter()
+            .filter(|c| c.contains(selector))
+            .cloned()
+            .collect();
+        if matched.is_empty() {
+            Err(format!(
+                "'{}' matches no configured command (available: {})",
+                selector,
+                self.commands.join(", ")
+            ))
+        } else {
+            Ok(matched)
+        }
+    }
+
+    /// Run `commands` in a fresh scratch tree.
+    pub(crate) async fn run(&self, commands: &[String]) -> Result<VerifyReport, String> {
+        let _running = self.running.lock().await;
+        let scratch = Scratch::create(&self.project_root).await?;
+
+        let mut reports = Vec::new();
+        for command in commands {
+            reports.push(self.run_command(command, &scratch.workdir).await);
+        }
+        scratch.cleanup().await;
+
+        Ok(VerifyReport {
+            passed: reports.iter().all(|r| r.passed),
+            commands: reports,
+        })
+    }
+
+    async fn run_command(&self, command: &str, workdir: &Path) -> CommandReport {
+        let started = Instant::now();
+        let report = |exit_code: Option<i32>, timed_out: bool, output: &str| CommandReport {
+            command: command.to_string(),
+            passed: exit_code == Some(0) && !timed_out,
+            exit_code,
+            timed_out,
+            duration_ms: started.elapsed().as_millis() as u64,
+            failing_tests: failing_tests(output),
+            output_tail: tail(output, OUTPUT_TAIL_BYTES),
+        };
+
+        // Commands come from the user's project config, so a shell is fine.
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(command)
+            .current_dir(workdir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(cache) = &self.cache_dir {
+            cmd.env("CARGO_TARGET_DIR", cache.join("cargo-target"));
+        }
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => return report(None, false, &format!("failed to spawn: {}", e)),
+        };
+        let pid = child.id();
+        let mut stdout = child.stdout.take().expect("piped stdout");
+        let mut stderr = child.stderr.take().expect("piped stderr");
+        let collect = async {
+            let mut out = Vec::new();
+            let mut err = Vec::new();
+            let (_, _, status) = tokio::join!(
+                stdout.read_to_end(&mut out),
+                stderr.read_to_end(&mut err),
+                child.wait()
+            );
+            out.extend_from_slice(&err);
+            (status, out)
+        };
+
+        match tokio::time::timeout(self.timeout, collect).await {
+            Ok((status, output)) => {
+                let output = String::from_utf8_lossy(&output);
+                report(status.ok().and_then(|s| s.code()), false, &output)
+            }
+            Err(_) => {
+                kill_group(pid).await;
+                report(
+                    None,
+                    true,
+                    &format!("timed out after {}s", self.timeout.as_secs()),
+                )
+            }
+        }
+    }
+}
+
+/// Kill the whole process group so build tools spawned by `sh` die too.
+async fn kill_group(pid: Option<u32>) {
+    #[cfg(unix)]
+    if let Some(pid) = pid {
+        let _ = Command::new("kill")
+            .args(["-KILL", &format!("-{}", pid)])
+            .status()
+            .await;
+    }
+    #[cfg(not(unix))]
+    let _ = pid;
+}
+
+/// Scratch checkout the commands run in
+struct Scratch {
+    dir: tempfile::TempDir,
+    /// Where commands run (mirrors the project's position inside the repo)
+    workdir: PathBuf,
+    /// Repository the worktree belongs to, if git was used
+    repo: Option<PathBuf>,
+}
+
+impl Scratch {
+    async fn create(project_root: &Path) -> Result<Self, String> {
+        let dir = tempfile::Builder::new()
+            .prefix("gugugaga-verify-")
+            .tempdir()
+            .map_err(|e| format!("failed to create scratch dir: {}", e))?;
+        let tree = dir.path().join("tree");
+
+        let repo = snapshot::repo_root(project_root).await;
+        let workdir = match &repo {
+            Some(repo) => {
+                let commit = snapshot::snapshot_commit(repo, "gugugaga verify snapshot")
+                    .await
+                    .map_err(|e| format!("failed to snapshot working tree: {}", e))?;
+                let tree_arg = tree.display().to_string();
+                snapshot::git(
+                    repo,
+                    &["worktree", "add", "--detach", "--quiet", &tree_arg, &commit],
+                    &[],
+                )
+                .await
+                .map_err(|e| format!("failed to create worktree: {}", e))?;
+                let relative = project_root
+                    .canonicalize()
+                    .ok()
+                    .and_then(|p| {
+                        let repo = repo.canonicalize().ok()?;
+                        p.strip_prefix(&repo).ok().map(Path::to_path_buf)
+                    })
+                    .unwrap_or_default();
+                tree.join(relative)
+            }
+            None => {
+                copy_tree(project_root, &tree)
+                    .map_err(|e| format!("failed to copy project: {}", e))?;
+                tree.clone()
+            }
+        };
+
+        for name in SHARED_DIRS {
+            let source = project_root.join(name);
+            let target = workdir.join(name);
+            if source.is_dir() && !target.exists() {
+                #[cfg(unix)]
+                let _ = std::os::unix::fs::symlink(&source, &target);
+            }
+        }
+
+        Ok(Self { dir, workdir, repo })
+    }
+
+    async fn cleanup(self) {
+        if let Some(repo) = &self.repo {
+            let tree = self.dir.path().join("tree").display().to_string();
+            let _ = snapshot::git(repo, &["worktree", "remove", "--force", &tree], &[]).await;
+            let _ = snapshot::git(repo, &["worktree", "prune"], &[]).await;
+        }
+    }
+}
+
+/// Copy a non-git project, skipping VCS metadata and build output.
+fn copy_tree(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+        if name_str == ".git" || name_str == "target" || SHARED_DIRS.contains(&name_str.as_ref()) {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        let dest = to.join(&name);
+        if file_type.is_dir() {
+            copy_tree(&entry.path(), &dest)?;
+        } else if file_type.is_file() {
+            std::fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Last `max` bytes of `text`, cut on a char boundary.
+fn tail(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut start = text.len() - max;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("...{}", &text[start..])
+}
+
+/// Extract failing test names from cargo, pytest, go and jest output.
+pub(crate) fn failing_tests(output: &str) -> Vec<String> {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        [
+            // cargo test
+            r"^test (\S+) \.\.\. FAILED$",
+            // pytest short summary
+            r"^FAILED (\S+)",
+            // go test
+            r"^\s*--- FAIL: (\S+)",
+            // jest
+            r"^\s*● (.+›.+)$",
+        ]
+        .iter()
+        .map(|p| Regex::new(p).expect("valid regex"))
+        .collect()
+    });
+
+    let mut names: Vec<String> = Vec::new();
+    for line in output.lines() {
+        let line = line.trim_end();
+        if let Some(name) = patterns
+            .iter()
+            .find_map(|re| re.captures(line))
+            .map(|caps| caps[1].trim().to_string())
+        {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+            if names.len() >= MAX_FAILING_TESTS {
+                break;
+            }
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn extracts_failing_test_names() {
+        let output = "\
+running 3 tests
+test ok_one ... ok
+test parser::tests::rejects ... FAILED
+test parser::tests::rejects ... FAILED
+FAILED tests/test_api.py::test_login - AssertionError
+    --- FAIL: TestHandler (0.00s)
+  ● Auth › refreshes tokens
+  ● Console
+";
+        assert_eq!(
+            failing_tests(output),
+            vec![
+                "parser::tests::rejects",
+                "tests/test_api.py::test_login",
+                "TestHandler",
+                "Auth › refreshes tokens",
+            ]
+        );
+    }
+
+    #[test]
+    fn selects_configured_commands() {
+        let config = ProjectConfig {
+            verify: vec!["cargo test".into(), "npm test".into()],
+            verify_timeout_secs: None,
+        };
+        let verifier = Verifier::new(PathBuf::from("."), &config);
+        assert_eq!(verifier.select("").unwrap().len(), 2);
+        assert_eq!(verifier.select("1").unwrap(), vec!["npm test"]);
+        assert_eq!(verifier.select("cargo").unwrap(), vec!["cargo test"]);
+        assert!(verifier.select("make").is_err());
+
+        let empty = Verifier::new(PathBuf::from("."), &ProjectConfig::default());
+        assert!(empty.select("").is_err());
+    }
+
+    #[tokio::test]
+    async fn runs_in_scratch_worktree_without_touching_project() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path();
+        snapshot::git(repo, &["init", "-q"], &[]).await.unwrap();
+        std::fs::write(repo.join("flag"), "ok\n").unwrap();
+
+        let config = ProjectConfig {
+            verify: vec![
+                "grep -q ok flag && touch created".into(),
+                "echo 'test it_breaks ... FAILED'; exit 3".into(),
+                "sleep 5".into(),
+            ],
+            verify_timeout_secs: Some(1),
+        };
+        let verifier = Verifier::new(repo.to_path_buf(), &config);
+        let report = verifier.run(&config.verify).await.unwrap();
+
+        assert!(!report.passed);
+        assert!(report.commands[0].passed);
+        assert_eq!(report.commands[1].exit_code, Some(3));
+        assert_eq!(report.commands[1].failing_tests, vec!["it_breaks"]);
+        assert!(report.commands[2].timed_out);
+        assert!(!repo.join("created").exists());
+        let worktrees = snapshot::git(repo, &["worktree", "list"], &[])
+            .await
+            .unwrap();
+        assert_eq!(worktrees.lines().count(), 1);
+    }
+}
@@ -5,8 +5,9 @@
 use crate::gugugaga_agent::{EvaluationResult, GugugagaAgent};
 use crate::memory::session_store;
 use crate::memory::{GugugagaNotebook, PersistentMemory, SessionStore, TurnRole};
+use crate::project_config::ProjectConfig;
 use crate::protocol::{self, notifications};
-use crate::rules::ViolationDetector;
+use crate::rules::{classify_command, ViolationDetector};
 use crate::{GugugagaConfig, GugugagaError, Result};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -79,9 +80,18 @@ impl Interceptor {
         let _ = session_store.cleanup(50).await;
         let session_store = Arc::new(session_store);
 
+        // Project-level settings (verification commands, ...)
+        let project_config = ProjectConfig::load(&config.cwd).unwrap_or_else(|e| {
+            warn!("Ignoring project config: {}", e);
+            ProjectConfig::default()
+        });
+
         // Initialize gugugaga agent
         let gugugaga_agent =
-            GugugagaAgent::new(&config.codex_home, memory.clone(), notebook.clone()).await?;
+            GugugagaAgent::new(&config.codex_home, memory.clone(), notebook.clone())
+                .await?
+                .with_read_roots(config.cwd.clone(), &config.extra_read_roots)
+                .with_verification(&project_config, project_dir.join("verify-cache"));
         let gugugaga_agent = Arc::new(gugugaga_agent);
 
         Ok(Self {
@@ -973,6 +983,47 @@ impl Interceptor {
                 }
             }
 
+            // Local destructive-command check. Never evaluated by the LLM and
+            // never auto-answered: risky requests always reach the user, with
+            // the classification attached for the approval overlay.
+            notifications::REQUEST_APPROVAL => {
+                let Some(params) = msg.get("params") else {
+                    return InterceptAction::Forward;
+                };
+                let Some(command) = params.get("command").and_then(|v| v.as_str()) else {
+                    return InterceptAction::Forward;
+                };
+                let cwd = params
+                    .get("cwd")
+                    .and_then(|v| v.as_str())
+                    .map(std::path::PathBuf::from)
+                    .unwrap_or_else(|| config.cwd.clone());
+                let risk = classify_command(command, &cwd, &config.cwd);
+                if risk.is_empty() {
+                    return InterceptAction::Forward;
+                }
+
+                info!(
+                    "Risky command awaiting approval ({}): {}",
+                    risk.summary(),
+                    command
+                );
+                {
+                    let mut mem = memory.write().await;
+                    let _ = mem
+                        .record_behavior(
+                            &format!("Approval requested for risky command ({})", risk.summary()),
+                            false,
+                        )
+                        .await;
+                }
+
+                let mut annotated = msg.clone();
+                annotated["params"]["gugugagaRisk"] =
+                    serde_json::to_value(&risk).unwrap_or_default();
+                InterceptAction::Replace(annotated.to_string())
+            }
+
             // Smart filter for user input requests
             notifications::REQUEST_USER_INPUT => {
                 if let Some(params) = msg.get("params") {
@@ -9,8 +9,10 @@ pub mod gugugaga_agent;
 pub mod interceptor;
 pub mod issues;
 pub mod memory;
+pub mod project_config;
 pub mod protocol;
 pub mod rules;
+pub mod snapshot;
 pub mod trust;
 pub mod tui;
 
@@ -38,6 +40,9 @@ pub struct GugugagaConfig {
 
     /// Whether to show verbose output including evaluations
     pub verbose: bool,
+
+    /// Directories outside `cwd` that supervisor file tools may read
+    pub extra_read_roots: Vec<PathBuf>,
 }
 
 impl GugugagaConfig {
@@ -59,6 +64,7 @@ impl GugugagaConfig {
             codex_home,
             strict_mode: false,
             verbose: false,
+            extra_read_roots: Vec::new(),
         }
     }
 
@@ -76,6 +82,11 @@ impl GugugagaConfig {
         self.verbose = verbose;
         self
     }
+
+    pub fn with_extra_read_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        self.extra_read_roots = roots;
+        self
+    }
 }
 
 /// Result type for Gugugaga operations
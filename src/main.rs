@@ -40,6 +40,10 @@ struct Cli {
     #[arg(long)]
     no_tui: bool,
 
+    /// Extra directory the supervisor's file tools may read (repeatable)
+    #[arg(long = "read-root", value_name = "DIR")]
+    read_roots: Vec<PathBuf>,
+
     /// Extra command groups
     #[command(subcommand)]
     command: Option<Commands>,
@@ -236,7 +240,8 @@ async fn run_tui_mode(
     // Create config
     let mut config = GugugagaConfig::new(cwd.clone(), codex_home)
         .with_strict_mode(cli.strict)
-        .with_verbose(cli.verbose);
+        .with_verbose(cli.verbose)
+        .with_extra_read_roots(cli.read_roots.clone());
 
     if let Some(memory_file) = cli.memory_file {
         config = config.with_memory_file(memory_file);
@@ -379,7 +384,8 @@ async fn run_plain_mode(cli: Cli, cwd: PathBuf, codex_home: PathBuf) -> anyhow::
     // Create config
     let mut config = GugugagaConfig::new(cwd.clone(), codex_home)
         .with_strict_mode(cli.strict)
-        .with_verbose(cli.verbose);
+        .with_verbose(cli.verbose)
+        .with_extra_read_roots(cli.read_roots.clone());
 
     if let Some(memory_file) = cli.memory_file {
         config = config.with_memory_file(memory_file);
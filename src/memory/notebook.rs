@@ -17,20 +17,15 @@ use tokio::fs;
 use tokio::io::AsyncWriteExt;
 
 /// Priority levels for attention items
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Priority {
     High,
+    #[default]
     Medium,
     Low,
 }
 
-impl Default for Priority {
-    fn default() -> Self {
-        Self::Medium
-    }
-}
-
 impl std::fmt::Display for Priority {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -42,7 +37,7 @@ impl std::fmt::Display for Priority {
 }
 
 /// Source of an attention item
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum AttentionSource {
     /// From user instruction
@@ -50,15 +45,10 @@ pub enum AttentionSource {
     /// From a past mistake
     Mistake,
     /// Inferred by the agent
+    #[default]
     Inference,
 }
 
-impl Default for AttentionSource {
-    fn default() -> Self {
-        Self::Inference
-    }
-}
-
 impl std::fmt::Display for AttentionSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
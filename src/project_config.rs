@@ -0,0 +1,81 @@
+//! Per-project settings read from `<project>/.gugugaga/config.toml`.
+//!
+//! Example:
+//!
+//! ```toml
+//! verify = ["cargo test --workspace", "npm test"]
+//! verify_timeout_secs = 900
+//! ```
+
+use crate::Result;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Directory holding project-level gugugaga files
+pub const PROJECT_DIR: &str = ".gugugaga";
+
+/// Default per-command limit for verification runs
+pub const DEFAULT_VERIFY_TIMEOUT_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ProjectConfig {
+    /// Build/test commands the supervisor may run to verify Codex's claims
+    pub verify: Vec<String>,
+
+    /// Per-command timeout for `verify`
+    pub verify_timeout_secs: Option<u64>,
+}
+
+impl ProjectConfig {
+    pub fn path(cwd: &Path) -> PathBuf {
+        cwd.join(PROJECT_DIR).join("config.toml")
+    }
+
+    /// Load the project config; a missing file yields the defaults.
+    pub fn load(cwd: &Path) -> Result<Self> {
+        let path = Self::path(cwd);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        toml::from_str(&content)
+            .map_err(|e| crate::GugugagaError::Memory(format!("Invalid {}: {}", path.display(), e)))
+    }
+
+    pub fn verify_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.verify_timeout_secs
+                .unwrap_or(DEFAULT_VERIFY_TIMEOUT_SECS),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn load_defaults_and_parses() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(
+            ProjectConfig::load(dir.path()).unwrap(),
+            ProjectConfig::default()
+        );
+
+        std::fs::create_dir(dir.path().join(PROJECT_DIR)).unwrap();
+        std::fs::write(
+            ProjectConfig::path(dir.path()),
+            "verify = [\"cargo test\"]\nverify_timeout_secs = 5\n",
+        )
+        .unwrap();
+        let config = ProjectConfig::load(dir.path()).unwrap();
+        assert_eq!(config.verify, vec!["cargo test".to_string()]);
+        assert_eq!(config.verify_timeout().as_secs(), 5);
+
+        std::fs::write(ProjectConfig::path(dir.path()), "verify = 3").unwrap();
+        assert!(ProjectConfig::load(dir.path()).is_err());
+    }
+}
@@ -1,5 +1,8 @@
 //! Rules and violation detection module
 
+pub mod command_risk;
+pub mod shell;
 mod violations;
 
+pub use command_risk::{classify_command, CommandRisk, RiskTag};
 pub use violations::{Violation, ViolationDetector, ViolationType};
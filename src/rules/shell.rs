@@ -0,0 +1,775 @@
+//! Minimal shell command parser for local (non-LLM) command analysis.
+//!
+//! This is not a complete POSIX grammar. It understands the subset that
+//! Codex actually emits — quoting, pipelines, `&&`/`||`/`;` lists, subshells,
+//! command/process substitution, here-documents and redirections — so callers
+//! can inspect every command a line would run without executing anything.
+
+use std::fmt;
+
+/// Error produced when a command line cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellParseError(pub String);
+
+impl fmt::Display for ShellParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "shell parse error: {}", self.0)
+    }
+}
+
+impl std::error::Error for ShellParseError {}
+
+/// A single shell word after quote removal
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Word {
+    /// Word text with quotes removed; substitutions are kept as written
+    pub text: String,
+    /// Whether any part of the word was quoted or escaped
+    pub quoted: bool,
+    /// Whether the word contains unquoted glob characters (`*`, `?`, `[`)
+    pub glob: bool,
+    /// Whether the word expands a parameter (`$VAR`, `${VAR}`, `$((..))`)
+    pub variable: bool,
+    /// Command/process substitutions embedded in the word
+    pub substitutions: Vec<Script>,
+}
+
+impl Word {
+    /// Whether the word's value can only be known at run time
+    pub fn is_dynamic(&self) -> bool {
+        self.variable || !self.substitutions.is_empty()
+    }
+}
+
+/// Redirection operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectKind {
+    /// `<`
+    Input,
+    /// `>`, `>|`, `&>`
+    Output,
+    /// `>>`, `&>>`
+    Append,
+    /// `<>`
+    ReadWrite,
+    /// `>&`, `<&`
+    Duplicate,
+    /// `<<`, `<<-`
+    HereDoc,
+    /// `<<<`
+    HereString,
+}
+
+/// A redirection attached to a command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redirect {
+    pub fd: Option<u32>,
+    pub kind: RedirectKind,
+    pub target: Word,
+}
+
+/// A simple command: assignments, argument words and redirections
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimpleCommand {
+    /// Leading `NAME=value` assignments
+    pub assignments: Vec<Word>,
+    pub words: Vec<Word>,
+    pub redirects: Vec<Redirect>,
+}
+
+impl SimpleCommand {
+    /// Argument vector (program first) after quote removal
+    pub fn argv(&self) -> Vec<&str> {
+        self.words.iter().map(|w| w.text.as_str()).collect()
+    }
+}
+
+/// A command inside a pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Simple(SimpleCommand),
+    /// `( ... )` or `{ ...; }`
+    Subshell {
+        body: Script,
+        redirects: Vec<Redirect>,
+    },
+}
+
+/// Operator that ends a pipeline inside a list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListOp {
+    /// `;`, newline or end of input
+    #[default]
+    Seq,
+    /// `&&`
+    And,
+    /// `||`
+    Or,
+    /// `&`
+    Background,
+}
+
+/// Commands connected by `|`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pipeline {
+    pub commands: Vec<Command>,
+    /// How the pipeline connects to the one after it
+    pub terminator: ListOp,
+}
+
+/// A list of pipelines separated by `;`, `&&`, `||`, `&` or newlines
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Script {
+    pub pipelines: Vec<Pipeline>,
+}
+
+impl Script {
+    /// Every simple command in the script, including those nested in
+    /// subshells, substitutions and redirection targets.
+    pub fn simple_commands(&self) -> Vec<&SimpleCommand> {
+        let mut out = Vec::new();
+        self.collect_simple_commands(&mut out);
+        out
+    }
+
+    fn collect_simple_commands<'a>(&'a self, out: &mut Vec<&'a SimpleCommand>) {
+        fn collect_words<'a>(words: &'a [Word], out: &mut Vec<&'a SimpleCommand>) {
+            for word in words {
+                for sub in &word.substitutions {
+                    sub.collect_simple_commands(out);
+                }
+            }
+        }
+        fn collect_redirects<'a>(redirects: &'a [Redirect], out: &mut Vec<&'a SimpleCommand>) {
+            for redirect in redirects {
+                for sub in &redirect.target.substitutions {
+                    sub.collect_simple_commands(out);
+                }
+            }
+        }
+
+        for pipeline in &self.pipelines {
+            for command in &pipeline.commands {
+                match command {
+                    Command::Simple(simple) => {
+                        out.push(simple);
+                        collect_words(&simple.assignments, out);
+                        collect_words(&simple.words, out);
+                        collect_redirects(&simple.redirects, out);
+                    }
+                    Command::Subshell { body, redirects } => {
+                        body.collect_simple_commands(out);
+                        collect_redirects(redirects, out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse a command line into a [`Script`].
+pub fn parse(input: &str) -> Result<Script, ShellParseError> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+        pending_heredocs: Vec::new(),
+    };
+    let script = parser.parse_script(None)?;
+    if parser.pos < parser.chars.len() {
+        return Err(ShellParseError(format!(
+            "unexpected '{}'",
+            parser.chars[parser.pos]
+        )));
+    }
+    Ok(script)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// Here-document delimiters (and whether tabs are stripped) whose bodies
+    /// start after the next newline.
+    pending_heredocs: Vec<(String, bool)>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    fn skip_blanks(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == ' ' || c == '\t' {
+                self.pos += 1;
+            } else if c == '\\' && self.peek_at(1) == Some('\n') {
+                self.pos += 2;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn consume_newline(&mut self) {
+        self.pos += 1;
+        let pending = std::mem::take(&mut self.pending_heredocs);
+        for (delimiter, strip_tabs) in pending {
+            loop {
+                if self.pos >= self.chars.len() {
+                    return;
+                }
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.pos += 1;
+                }
+                let line: String = self.chars[start..self.pos].iter().collect();
+                if self.peek() == Some('\n') {
+                    self.pos += 1;
+                }
+                let line = if strip_tabs {
+                    line.trim_start_matches('\t')
+                } else {
+                    line.as_str()
+                };
+                if line == delimiter {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn parse_script(&mut self, terminator: Option<char>) -> Result<Script, ShellParseError> {
+        let mut script = Script::default();
+        loop {
+            self.skip_blanks();
+            match self.peek() {
+                None => break,
+                Some('\n') | Some(';') => {
+                    if self.peek() == Some('\n') {
+                        self.consume_newline();
+                    } else {
+                        self.pos += 1;
+                    }
+                    continue;
+                }
+                Some('#') => {
+                    self.skip_comment();
+                    continue;
+                }
+                Some(c) if Some(c) == terminator => break,
+                Some(')') => return Err(ShellParseError("unexpected ')'".to_string())),
+                _ => {}
+            }
+
+            let mut pipeline = self.parse_pipeline()?;
+            self.skip_blanks();
+            if self.starts_with("&&") {
+                pipeline.terminator = ListOp::And;
+                self.pos += 2;
+            } else if self.starts_with("||") {
+                pipeline.terminator = ListOp::Or;
+                self.pos += 2;
+            } else if self.peek() == Some('&') {
+                pipeline.terminator = ListOp::Background;
+                self.pos += 1;
+            } else if self.peek() == Some(';') {
+                self.pos += 1;
+            }
+            script.pipelines.push(pipeline);
+        }
+        Ok(script)
+    }
+
+    fn parse_pipeline(&mut self) -> Result<Pipeline, ShellParseError> {
+        let mut pipeline = Pipeline::default();
+        loop {
+            self.skip_blanks();
+            if self.at_word("!") {
+                self.pos += 1;
+                self.skip_blanks();
+            }
+            pipeline.commands.push(self.parse_command()?);
+            self.skip_blanks();
+            if self.peek() == Some('|') && self.peek_at(1) != Some('|') {
+                self.pos += 1;
+                if self.peek() == Some('&') {
+                    self.pos += 1;
+                }
+                // Allow a line break after the pipe operator.
+                loop {
+                    self.skip_blanks();
+                    if self.peek() == Some('\n') {
+                        self.consume_newline();
+                    } else {
+                        break;
+                    }
+                }
+                continue;
+            }
+            break;
+        }
+        Ok(pipeline)
+    }
+
+    /// Whether the next token is exactly the given reserved word.
+    fn at_word(&self, word: &str) -> bool {
+        self.starts_with(word)
+            && self
+                .peek_at(word.chars().count())
+                .is_none_or(|c| c.is_whitespace() || matches!(c, ';' | '&' | '|' | ')'))
+    }
+
+    fn parse_command(&mut self) -> Result<Command, ShellParseError> {
+        self.skip_blanks();
+        if self.peek() == Some('(') && self.peek_at(1) != Some('(') {
+            self.pos += 1;
+            let body = self.parse_script(Some(')'))?;
+            self.expect(')')?;
+            let redirects = self.parse_trailing_redirects()?;
+            return Ok(Command::Subshell { body, redirects });
+        }
+        if self.at_word("{") {
+            self.pos += 1;
+            let body = self.parse_script(Some('}'))?;
+            self.expect('}')?;
+            let redirects = self.parse_trailing_redirects()?;
+            return Ok(Command::Subshell { body, redirects });
+        }
+
+        let mut command = SimpleCommand::default();
+        loop {
+            self.skip_blanks();
+            let Some(c) = self.peek() else { break };
+            if matches!(c, '|' | ';' | '\n' | ')') {
+                break;
+            }
+            if c == '&' && self.peek_at(1) != Some('>') {
+                break;
+            }
+            if c == '#' {
+                self.skip_comment();
+                break;
+            }
+            if let Some(redirect) = self.try_parse_redirect()? {
+                command.redirects.push(redirect);
+                continue;
+            }
+            let word = self.parse_word()?;
+            if command.words.is_empty() && is_assignment(&word) {
+                command.assignments.push(word);
+            } else {
+                command.words.push(word);
+            }
+        }
+
+        if command.words.is_empty()
+            && command.assignments.is_empty()
+            && command.redirects.is_empty()
+        {
+            return Err(ShellParseError(match self.peek() {
+                Some(c) => format!("expected a command before '{}'", c),
+                None => "expected a command".to_string(),
+            }));
+        }
+        Ok(Command::Simple(command))
+    }
+
+    fn parse_trailing_redirects(&mut self) -> Result<Vec<Redirect>, ShellParseError> {
+        let mut redirects = Vec::new();
+        loop {
+            self.skip_blanks();
+            match self.try_parse_redirect()? {
+                Some(redirect) => redirects.push(redirect),
+                None => break,
+            }
+        }
+        Ok(redirects)
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ShellParseError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(ShellParseError(format!("expected '{}'", c)))
+        }
+    }
+
+    fn try_parse_redirect(&mut self) -> Result<Option<Redirect>, ShellParseError> {
+        let start = self.pos;
+        let mut digits = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit()) {
+            digits.push(c);
+            self.pos += 1;
+        }
+        let fd = digits.parse::<u32>().ok();
+
+        // Process substitution is a word, not a redirection.
+        if digits.is_empty()
+            && matches!(self.peek(), Some('<') | Some('>'))
+            && self.peek_at(1) == Some('(')
+        {
+            return Ok(None);
+        }
+
+        let (kind, len) = if digits.is_empty() && self.starts_with("&>>") {
+            (RedirectKind::Append, 3)
+        } else if digits.is_empty() && self.starts_with("&>") {
+            (RedirectKind::Output, 2)
+        } else if self.starts_with("<<<") {
+            (RedirectKind::HereString, 3)
+        } else if self.starts_with("<<-") || self.starts_with("<<") {
+            let len = if self.starts_with("<<-") { 3 } else { 2 };
+            (RedirectKind::HereDoc, len)
+        } else if self.starts_with(">>") {
+            (RedirectKind::Append, 2)
+        } else if self.starts_with(">&") || self.starts_with("<&") {
+            (RedirectKind::Duplicate, 2)
+        } else if self.starts_with(">|") {
+            (RedirectKind::Output, 2)
+        } else if self.starts_with("<>") {
+            (RedirectKind::ReadWrite, 2)
+        } else if self.starts_with(">") {
+            (RedirectKind::Output, 1)
+        } else if self.starts_with("<") {
+            (RedirectKind::Input, 1)
+        } else {
+            self.pos = start;
+            return Ok(None);
+        };
+        let strip_tabs = kind == RedirectKind::HereDoc && len == 3;
+        self.pos += len;
+        self.skip_blanks();
+        if self
+            .peek()
+            .is_none_or(|c| matches!(c, '\n' | ';' | '|' | '&' | ')' | '<' | '>'))
+        {
+            return Err(ShellParseError("missing redirection target".to_string()));
+        }
+        let target = self.parse_word()?;
+        if kind == RedirectKind::HereDoc {
+            self.pending_heredocs
+                .push((target.text.clone(), strip_tabs));
+        }
+        Ok(Some(Redirect { fd, kind, target }))
+    }
+
+    fn parse_word(&mut self) -> Result<Word, ShellParseError> {
+        let mut word = Word::default();
+        let at_start = self.pos;
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' | '\n' | ';' | '|' | '&' | ')' => break,
+                '(' if self.pos == at_start => {
+                    return Err(ShellParseError("unexpected '('".to_string()));
+                }
+                '<' | '>' if self.peek_at(1) == Some('(') => {
+                    self.pos += 2;
+                    let sub = self.parse_script(Some(')'))?;
+                    self.expect(')')?;
+                    word.text.push(c);
+                    word.text.push_str("(...)");
+                    word.substitutions.push(sub);
+                }
+                '<' | '>' | '(' => break,
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(escaped) => {
+                            word.text.push(escaped);
+                            word.quoted = true;
+                            self.pos += 1;
+                        }
+                        None => word.text.push('\\'),
+                    }
+                }
+                '\'' => {
+                    self.pos += 1;
+                    word.quoted = true;
+                    loop {
+                        match self.peek() {
+                            Some('\'') => {
+                                self.pos += 1;
+                                break;
+                            }
+                            Some(inner) => {
+                                word.text.push(inner);
+                                self.pos += 1;
+                            }
+                            None => {
+                                return Err(ShellParseError("unterminated single quote".into()))
+                            }
+                        }
+                    }
+                }
+                '"' => {
+                    self.pos += 1;
+                    word.quoted = true;
+                    self.parse_double_quoted(&mut word)?;
+                }
+                '$' => self.parse_dollar(&mut word)?,
+                '`' => self.parse_backtick(&mut word)?,
+                '*' | '?' | '[' => {
+                    word.glob = true;
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+                _ => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        Ok(word)
+    }
+
+    fn parse_double_quoted(&mut self, word: &mut Word) -> Result<(), ShellParseError> {
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(c @ ('$' | '`' | '"' | '\\')) => {
+                            word.text.push(c);
+                            self.pos += 1;
+                        }
+                        Some('\n') => self.pos += 1,
+                        _ => word.text.push('\\'),
+                    }
+                }
+                Some('$') => self.parse_dollar(word)?,
+                Some('`') => self.parse_backtick(word)?,
+                Some(c) => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(ShellParseError("unterminated double quote".into())),
+            }
+        }
+    }
+
+    fn parse_dollar(&mut self, word: &mut Word) -> Result<(), ShellParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        match self.peek() {
+            Some('(') if self.peek_at(1) == Some('(') => {
+                // Arithmetic expansion: skip to the matching `))`.
+                self.pos += 2;
+                let mut depth = 0usize;
+                loop {
+                    match self.peek() {
+                        Some('(') => depth += 1,
+                        Some(')') if depth == 0 && self.peek_at(1) == Some(')') => {
+                            self.pos += 2;
+                            break;
+                        }
+                        Some(')') => depth = depth.saturating_sub(1),
+                        None => {
+                            return Err(ShellParseError("unterminated arithmetic expansion".into()))
+                        }
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+                word.variable = true;
+            }
+            Some('(') => {
+                self.pos += 1;
+                let sub = self.parse_script(Some(')'))?;
+                self.expect(')')?;
+                word.substitutions.push(sub);
+            }
+            Some('{') => {
+                while let Some(c) = self.peek() {
+                    self.pos += 1;
+                    if c == '}' {
+                        break;
+                    }
+                }
+                if self.chars[self.pos - 1] != '}' {
+                    return Err(ShellParseError("unterminated parameter expansion".into()));
+                }
+                word.variable = true;
+            }
+            Some(c) if c.is_ascii_alphanumeric() || matches!(c, '_' | '@' | '*' | '#' | '?') => {
+                if c.is_ascii_alphabetic() || c == '_' {
+                    while self
+                        .peek()
+                        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+                    {
+                        self.pos += 1;
+                    }
+                } else {
+                    self.pos += 1;
+                }
+                word.variable = true;
+            }
+            Some('\'') => {
+                // ANSI-C quoting: $'...'
+                self.pos += 1;
+                word.quoted = true;
+                loop {
+                    match self.peek() {
+                        Some('\\') => {
+                            self.pos += 1;
+                            if let Some(c) = self.peek() {
+                                word.text.push(c);
+                                self.pos += 1;
+                            }
+                        }
+                        Some('\'') => {
+                            self.pos += 1;
+                            return Ok(());
+                        }
+                        Some(c) => {
+                            word.text.push(c);
+                            self.pos += 1;
+                        }
+                        None => return Err(ShellParseError("unterminated $'...' quote".into())),
+                    }
+                }
+            }
+            _ => {
+                word.text.push('$');
+                return Ok(());
+            }
+        }
+        word.text.extend(&self.chars[start..self.pos]);
+        Ok(())
+    }
+
+    fn parse_backtick(&mut self, word: &mut Word) -> Result<(), ShellParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut inner = String::new();
+        loop {
+            match self.peek() {
+                Some('`') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    if let Some(c) = self.peek() {
+                        if !matches!(c, '`' | '\\' | '$') {
+                            inner.push('\\');
+                        }
+                        inner.push(c);
+                        self.pos += 1;
+                    }
+                }
+                Some(c) => {
+                    inner.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(ShellParseError("unterminated backquote".into())),
+            }
+        }
+        word.substitutions.push(parse(&inner)?);
+        word.text.extend(&self.chars[start..self.pos]);
+        Ok(())
+    }
+}
+
+fn is_assignment(word: &Word) -> bool {
+    let Some((name, _)) = word.text.split_once('=') else {
+        return false;
+    };
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argvs(input: &str) -> Vec<Vec<String>> {
+        parse(input)
+            .unwrap()
+            .simple_commands()
+            .into_iter()
+            .map(|c| c.argv().into_iter().map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_lists_pipelines_and_quotes() {
+        let script = parse("cd 'my dir' && ls -la | grep \"a b\"; echo done || true &").unwrap();
+        assert_eq!(script.pipelines.len(), 4);
+        assert_eq!(script.pipelines[1].commands.len(), 2);
+        let ops: Vec<ListOp> = script.pipelines.iter().map(|p| p.terminator).collect();
+        assert_eq!(
+            ops,
+            vec![ListOp::And, ListOp::Seq, ListOp::Or, ListOp::Background]
+        );
+        assert_eq!(
+            argvs("cd 'my dir' && ls -la | grep \"a b\""),
+            vec![
+                vec!["cd".to_string(), "my dir".to_string()],
+                vec!["ls".to_string(), "-la".to_string()],
+                vec!["grep".to_string(), "a b".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subshells_and_substitutions_are_flattened() {
+        let commands = argvs("(cd /tmp && rm -rf x) ; echo $(whoami) `date` <(cat f)");
+        let programs: Vec<&str> = commands.iter().map(|c| c[0].as_str()).collect();
+        assert_eq!(programs, vec!["cd", "rm", "echo", "whoami", "date", "cat"]);
+    }
+
+    #[test]
+    fn test_redirects_assignments_and_flags() {
+        let script = parse("FOO=1 cargo test 2>&1 > out.log *.rs $HOME").unwrap();
+        let commands = script.simple_commands();
+        let cmd = commands[0];
+        assert_eq!(cmd.assignments[0].text, "FOO=1");
+        assert_eq!(cmd.argv(), vec!["cargo", "test", "*.rs", "$HOME"]);
+        assert_eq!(cmd.redirects.len(), 2);
+        assert_eq!(cmd.redirects[0].kind, RedirectKind::Duplicate);
+        assert_eq!(cmd.redirects[0].fd, Some(2));
+        assert_eq!(cmd.redirects[1].kind, RedirectKind::Output);
+        assert!(cmd.words[2].glob);
+        assert!(cmd.words[3].variable);
+    }
+
+    #[test]
+    fn test_heredoc_body_is_skipped() {
+        let commands = argvs("cat <<EOF\nrm -rf /\nEOF\necho ok");
+        let programs: Vec<&str> = commands.iter().map(|c| c[0].as_str()).collect();
+        assert_eq!(programs, vec!["cat", "echo"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("echo 'unterminated").is_err());
+        assert!(parse("echo $(ls").is_err());
+        assert!(parse("echo )").is_err());
+        assert!(parse("| grep x").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Git snapshots of the working tree.
+//!
+//! A snapshot is a commit built through a throwaway index, so it captures
+//! tracked edits and untracked (non-ignored) files without touching the
+//! user's index, branch or stash.
+
+use crate::{GugugagaError, Result};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+const SNAPSHOT_AUTHOR: &str = "gugugaga";
+const SNAPSHOT_EMAIL: &str = "gugugaga@localhost";
+
+/// Top-level directory of the git repository containing `cwd`, if any.
+pub async fn repo_root(cwd: &Path) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(cwd)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!root.is_empty()).then(|| PathBuf::from(root))
+}
+
+/// Run git in `repo` and return trimmed stdout.
+pub async fn git(repo: &Path, args: &[&str], envs: &[(&str, &Path)]) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(args).current_dir(repo);
+    for (key, value) in envs {
+        cmd.env(key, value);
+    }
+    let output = cmd.output().await?;
+    if !output.status.success() {
+        return Err(GugugagaError::Io(std::io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Commit the current working tree of `repo` (parented on HEAD when there
+/// is one) and return the commit id. No ref is updated.
+pub async fn snapshot_commit(repo: &Path, message: &str) -> Result<String> {
+    let scratch = tempfile::TempDir::new()?;
+    let index = scratch.path().join("index");
+    let env = [("GIT_INDEX_FILE", index.as_path())];
+
+    let head = git(repo, &["rev-parse", "--verify", "-q", "HEAD"], &[])
+        .await
+        .ok()
+        .filter(|h| !h.is_empty());
+    if head.is_some() {
+        git(repo, &["read-tree", "HEAD"], &env).await?;
+    }
+    git(repo, &["add", "-A", "--", "."], &env).await?;
+    let tree = git(repo, &["write-tree"], &env).await?;
+
+    let mut args = vec!["commit-tree", tree.as_str(), "-m", message];
+    if let Some(head) = head.as_deref() {
+        args.extend(["-p", head]);
+    }
+    let mut cmd = Command::new("git");
+    cmd.args(&args)
+        .current_dir(repo)
+        .env("GIT_AUTHOR_NAME", SNAPSHOT_AUTHOR)
+        .env("GIT_AUTHOR_EMAIL", SNAPSHOT_EMAIL)
+        .env("GIT_COMMITTER_NAME", SNAPSHOT_AUTHOR)
+        .env("GIT_COMMITTER_EMAIL", SNAPSHOT_EMAIL);
+    let output = cmd.output().await?;
+    if !output.status.success() {
+        return Err(GugugagaError::Io(std::io::Error::other(format!(
+            "git commit-tree failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn snapshot_includes_untracked_without_touching_index() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path();
+        git(repo, &["init", "-q"], &[]).await.unwrap();
+        assert!(repo_root(repo).await.is_some());
+
+        std::fs::write(repo.join("tracked.txt"), "one\n").unwrap();
+        std::fs::write(repo.join(".gitignore"), "ignored.txt\n").unwrap();
+        let first = snapshot_commit(repo, "first").await.unwrap();
+
+        std::fs::write(repo.join("tracked.txt"), "two\n").unwrap();
+        std::fs::write(repo.join("new.txt"), "new\n").unwrap();
+        std::fs::write(repo.join("ignored.txt"), "x\n").unwrap();
+        let second = snapshot_commit(repo, "second").await.unwrap();
+
+        let files = git(repo, &["ls-tree", "--name-only", &second], &[])
+            .await
+            .unwrap();
+        assert!(files.contains("new.txt"));
+        assert!(!files.contains("ignored.txt"));
+        let shown = git(repo, &["show", &format!("{}:tracked.txt", first)], &[])
+            .await
+            .unwrap();
+        assert_eq!(shown, "one");
+        // The real index stays empty and no branch was created.
+        assert_eq!(git(repo, &["ls-files"], &[]).await.unwrap(), "");
+        assert!(git(repo, &["rev-parse", "--verify", "-q", "HEAD"], &[])
+            .await
+            .is_err());
+    }
+}
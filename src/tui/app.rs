@@ -21,6 +21,7 @@ use ratatui::{
 use tokio::sync::{mpsc, RwLock};
 
 use crate::memory::GugugagaNotebook;
+use crate::rules::CommandRisk;
 
 /// Convert an absolute file path to a relative path based on cwd.
 fn make_relative_path(raw_path: &str, cwd: &str) -> String {
@@ -201,6 +202,64 @@ fn format_notebook_diff_for_display(diff: &serde_json::Value) -> Vec<String> {
     lines
 }
 
+fn format_verify_report(report: &serde_json::Value) -> String {
+    let passed = report
+        .get("passed")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let mut lines = vec![if passed {
+        "🧪 Verify: all checks passed".to_string()
+    } else {
+        "🧪 Verify: FAILED".to_string()
+    }];
+    let commands = report
+        .get("commands")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for command in &commands {
+        let name = command
+            .get("command")
+            .and_then(|v| v.as_str())
+            .unwrap_or("?");
+        let ok = command.get("passed").and_then(|v| v.as_bool()) == Some(true);
+        let seconds = command
+            .get("duration_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+            / 1000;
+        let outcome = if ok {
+            "pass".to_string()
+        } else if command.get("timed_out").and_then(|v| v.as_bool()) == Some(true) {
+            "timed out".to_string()
+        } else {
+            match command.get("exit_code").and_then(|v| v.as_i64()) {
+                Some(code) => format!("exit {}", code),
+                None => "killed".to_string(),
+            }
+        };
+        lines.push(format!(
+            "  {} `{}` ({}, {}s)",
+            if ok { "✓" } else { "✗" },
+            name,
+            outcome,
+            seconds
+        ));
+        let failing = command
+            .get("failing_tests")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        for test in failing.iter().take(10).filter_map(|t| t.as_str()) {
+            lines.push(format!("    - {}", test));
+        }
+        if failing.len() > 10 {
+            lines.push(format!("    ... ({} more)", failing.len() - 10));
+        }
+    }
+    lines.join("\n")
+}
+
 use super::ascii_animation::AsciiAnimation;
 use super::clipboard_paste::paste_image_to_temp_png;
 use super::input::{InputAction, InputState};
@@ -426,6 +485,16 @@ struct PendingApproval {
     /// For command exec: proposed execpolicy amendment prefix (e.g. ["echo"])
     /// When present, the user can choose "don't ask again for similar commands"
     proposed_execpolicy_amendment: Option<Vec<String>>,
+    /// Destructive-command classification attached by the interceptor
+    risk: Option<CommandRisk>,
+}
+
+impl PendingApproval {
+    /// Risky commands need a deliberate per-command `y`: no Enter shortcut
+    /// and no "don't ask again" amendment.
+    fn requires_explicit_approval(&self) -> bool {
+        self.risk.as_ref().is_some_and(|r| r.requires_human)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1204,6 +1273,11 @@ impl App {
         // Handle approval dialog first (highest priority — modal overlay)
         if let Some(approval) = self.pending_approval.take() {
             match key.code {
+                // Enter is not enough for a risky command — require an explicit y
+                crossterm::event::KeyCode::Enter if approval.requires_explicit_approval() => {
+                    self.pending_approval = Some(approval);
+                    return;
+                }
                 // y / Enter — Accept (approve this time)
                 crossterm::event::KeyCode::Char('y')
                 | crossterm::event::KeyCode::Char('Y')
@@ -1217,7 +1291,8 @@ impl App {
                 // p — Accept and don't ask again for similar commands (exec only, needs amendment)
                 crossterm::event::KeyCode::Char('p') | crossterm::event::KeyCode::Char('P')
                     if matches!(approval.approval_type, ApprovalType::CommandExecution)
-                        && approval.proposed_execpolicy_amendment.is_some() =>
+                        && approval.proposed_execpolicy_amendment.is_some()
+                        && !approval.requires_explicit_approval() =>
                 {
                     let amendment = approval.proposed_execpolicy_amendment.clone().unwrap();
                     let prefix = amendment.join(" ");
@@ -1683,16 +1758,14 @@ impl App {
             InputAction::ScrollDown => {
                 // Do not scroll message pane from composer keys.
             }
-            InputAction::HistoryPrev => {
-                if self.input.should_handle_history_navigation() {
-                    let _ = self.input.navigate_history_prev();
-                }
+            InputAction::HistoryPrev if self.input.should_handle_history_navigation() => {
+                let _ = self.input.navigate_history_prev();
             }
-            InputAction::HistoryNext => {
-                if self.input.should_handle_history_navigation() {
-                    let _ = self.input.navigate_history_next();
-                }
+            InputAction::HistoryPrev => {}
+            InputAction::HistoryNext if self.input.should_handle_history_navigation() => {
+                let _ = self.input.navigate_history_next();
             }
+            InputAction::HistoryNext => {}
             InputAction::Tab => {
                 self.handle_tab_completion();
             }
@@ -4826,6 +4899,11 @@ Make it comprehensive but concise."#;
                                     .collect::<Vec<_>>()
                             });
 
+                        let risk = params
+                            .get("gugugagaRisk")
+                            .and_then(|v| serde_json::from_value::<CommandRisk>(v.clone()).ok())
+                            .filter(|r| !r.is_empty());
+
                         self.pending_approval = Some(PendingApproval {
                             request_id: id,
                             approval_type: ApprovalType::CommandExecution,
@@ -4834,6 +4912,7 @@ Make it comprehensive but concise."#;
                             reason: reason.clone(),
                             changes: vec![],
                             proposed_execpolicy_amendment: proposed_amendment,
+                            risk,
                         });
                         self.approval_scroll = 0;
 
@@ -4857,6 +4936,7 @@ Make it comprehensive but concise."#;
                             reason: reason.clone(),
                             changes: vec![],
                             proposed_execpolicy_amendment: None,
+                            risk: None,
                         });
                         self.approval_scroll = 0;
 
@@ -5921,6 +6001,39 @@ Make it comprehensive but concise."#;
                         self.scroll_to_bottom();
                     }
                 }
+                "gugugaga/verify" => {
+                    let params = json.get("params");
+                    let status = params
+                        .and_then(|p| p.get("status"))
+                        .and_then(|s| s.as_str())
+                        .unwrap_or("");
+                    match status {
+                        "started" => {
+                            self.gugugaga_status = Some("Verifying build/tests...".to_string());
+                        }
+                        "completed" => {
+                            self.gugugaga_status = None;
+                            if let Some(report) = params.and_then(|p| p.get("report")) {
+                                self.messages
+                                    .push(Message::gugugaga(format_verify_report(report)));
+                                self.scroll_to_bottom();
+                            }
+                        }
+                        "failed" => {
+                            self.gugugaga_status = None;
+                            let error = params
+                                .and_then(|p| p.get("error"))
+                                .and_then(|e| e.as_str())
+                                .unwrap_or("unknown error");
+                            self.messages.push(Message::gugugaga(format!(
+                                "🧪 Verify failed to run: {}",
+                                error
+                            )));
+                            self.scroll_to_bottom();
+                        }
+                        _ => {}
+                    }
+                }
                 "gugugaga/status" => {
                     // Silently acknowledge gugugaga status — no message shown
                 }
@@ -7489,17 +7602,35 @@ Make it comprehensive but concise."#;
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD);
         let desc_style = Style::default().fg(Color::White);
+        let explicit_only = approval.requires_explicit_approval();
+        let accent = if explicit_only {
+            Color::Red
+        } else {
+            Color::Yellow
+        };
 
         // --- Build footer lines (options) — these are ALWAYS visible ---
         let mut footer_lines: Vec<Line> = Vec::new();
         footer_lines.push(Line::from(vec![
             Span::styled("  [y] ", opt_style),
             Span::styled("Yes, proceed", desc_style),
+            Span::styled(
+                if explicit_only {
+                    "  (Enter disabled for risky commands)"
+                } else {
+                    ""
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
         ]));
 
         match approval.approval_type {
             ApprovalType::CommandExecution => {
-                if let Some(ref amendment) = approval.proposed_execpolicy_amendment {
+                if let Some(amendment) = approval
+                    .proposed_execpolicy_amendment
+                    .as_ref()
+                    .filter(|_| !explicit_only)
+                {
                     let prefix = amendment.join(" ");
                     footer_lines.push(Line::from(vec![
                         Span::styled("  [p] ", opt_style),
@@ -7552,6 +7683,25 @@ Make it comprehensive but concise."#;
             content_lines.push(Line::from(""));
         }
 
+        if let Some(ref risk) = approval.risk {
+            let mut spans = vec![Span::styled(
+                "⚠ RISK ",
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(accent)
+                    .add_modifier(Modifier::BOLD),
+            )];
+            for tag in &risk.tags {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    format!(" {} ", tag.label()),
+                    Style::default().fg(accent).add_modifier(Modifier::BOLD),
+                ));
+            }
+            content_lines.push(Line::from(spans));
+            content_lines.push(Line::from(""));
+        }
+
         match approval.approval_type {
             ApprovalType::CommandExecution => {
                 let cmd = approval.command.as_deref().unwrap_or("(unknown)");
@@ -7599,12 +7749,10 @@ Make it comprehensive but concise."#;
         // Outer border
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow))
+            .border_style(Style::default().fg(accent))
             .title_top(Line::styled(
                 " ⚡ APPROVAL REQUIRED ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(accent).add_modifier(Modifier::BOLD),
             ));
         let inner = block.inner(overlay_area);
         f.render_widget(block, overlay_area);